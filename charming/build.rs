@@ -0,0 +1,47 @@
+fn main() {
+    // Without any rerun directive, the script would run again on every change
+    // to the package.
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "ssr-snapshot")]
+    snapshot();
+}
+
+/// Creates a V8 startup snapshot with the runtime, echarts and the built-in
+/// themes already evaluated, which is embedded into the `ImageRenderer`.
+#[cfg(feature = "ssr-snapshot")]
+fn snapshot() {
+    const SCRIPTS: &[&str] = &[
+        "runtime.js",
+        "echarts-5.5.1.min.js",
+        "theme-vintage.js",
+        "theme-westeros.js",
+        "theme-essos.js",
+        "theme-wonderland.js",
+        "theme-walden.js",
+        "theme-chalk.js",
+        "theme-infographic.js",
+        "theme-macarons.js",
+        "theme-roma.js",
+        "theme-shine.js",
+        "theme-purple-passion.js",
+        "theme-halloween.js",
+    ];
+
+    let scripts = SCRIPTS
+        .iter()
+        .map(|name| {
+            let path = format!("src/asset/{name}");
+            println!("cargo:rerun-if-changed={path}");
+            let source = std::fs::read_to_string(&path)
+                .unwrap_or_else(|error| panic!("failed to read {path}: {error}"));
+            (name.to_string(), source)
+        })
+        .collect();
+
+    let snapshot = charming_snapshot::create(scripts)
+        .unwrap_or_else(|error| panic!("failed to create the echarts snapshot: {error}"));
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is not set");
+    std::fs::write(format!("{out_dir}/ECHARTS_SNAPSHOT.bin"), snapshot)
+        .expect("failed to write the echarts snapshot");
+}
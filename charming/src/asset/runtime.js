@@ -14,6 +14,143 @@
         },
     }
 
+    // echarts parses SVG maps registered with `echarts.registerMap` through
+    // `DOMParser`, which is not available in this runtime. This is a minimal
+    // XML parser providing the small part of the DOM API echarts relies on.
+    function decodeEntities(text) {
+        return text.replace(/&(#x[0-9a-fA-F]+|#[0-9]+|lt|gt|amp|quot|apos);/g, (_, entity) => {
+            switch (entity) {
+                case "lt": return "<";
+                case "gt": return ">";
+                case "amp": return "&";
+                case "quot": return "\"";
+                case "apos": return "'";
+            }
+            return String.fromCodePoint(entity[1] === "x"
+                ? parseInt(entity.slice(2), 16)
+                : parseInt(entity.slice(1), 10));
+        });
+    }
+
+    class XmlNode {
+        constructor(nodeType, nodeName, data) {
+            this.nodeType = nodeType;
+            this.nodeName = nodeName;
+            this.data = data || "";
+            this.attributes = {};
+            this.parentNode = null;
+            this.childNodes = [];
+            this.firstChild = null;
+            this.lastChild = null;
+            this.nextSibling = null;
+        }
+
+        appendChild(node) {
+            node.parentNode = this;
+            if (this.lastChild) {
+                this.lastChild.nextSibling = node;
+            } else {
+                this.firstChild = node;
+            }
+            this.lastChild = node;
+            this.childNodes.push(node);
+            return node;
+        }
+
+        getAttribute(name) {
+            return Object.prototype.hasOwnProperty.call(this.attributes, name)
+                ? this.attributes[name]
+                : null;
+        }
+
+        get textContent() {
+            if (this.nodeType === 3) {
+                return this.data;
+            }
+            return this.childNodes.map((node) => node.textContent).join("");
+        }
+    }
+
+    function skipPast(source, token, from) {
+        const index = source.indexOf(token, from);
+        return index === -1 ? source.length : index + token.length;
+    }
+
+    function parseXml(source) {
+        const document = new XmlNode(9, "#document");
+        const attributePattern = /([^\s=\/>]+)\s*=\s*(?:"([^"]*)"|'([^']*)')/g;
+        let parent = document;
+        let pos = 0;
+
+        while (pos < source.length) {
+            const start = source.indexOf("<", pos);
+            const textEnd = start === -1 ? source.length : start;
+            if (textEnd > pos && parent !== document) {
+                parent.appendChild(new XmlNode(3, "#text", decodeEntities(source.slice(pos, textEnd))));
+            }
+            if (start === -1) {
+                break;
+            }
+
+            if (source.startsWith("<!--", start)) {
+                pos = skipPast(source, "-->", start + 4);
+            } else if (source.startsWith("<![CDATA[", start)) {
+                const end = source.indexOf("]]>", start + 9);
+                const data = source.slice(start + 9, end === -1 ? source.length : end);
+                parent.appendChild(new XmlNode(3, "#text", data));
+                pos = skipPast(source, "]]>", start + 9);
+            } else if (source.startsWith("<?", start)) {
+                pos = skipPast(source, "?>", start + 2);
+            } else if (source.startsWith("<!", start)) {
+                // A doctype declaration, possibly with an internal subset.
+                const bracket = source.indexOf("[", start);
+                const close = source.indexOf(">", start);
+                pos = bracket !== -1 && bracket < close
+                    ? skipPast(source, ">", skipPast(source, "]", bracket))
+                    : skipPast(source, ">", start);
+            } else if (source[start + 1] === "/") {
+                parent = parent.parentNode || document;
+                pos = skipPast(source, ">", start);
+            } else {
+                let end = start + 1;
+                let quote = null;
+                for (; end < source.length; end++) {
+                    const char = source[end];
+                    if (quote) {
+                        if (char === quote) {
+                            quote = null;
+                        }
+                    } else if (char === "\"" || char === "'") {
+                        quote = char;
+                    } else if (char === ">") {
+                        break;
+                    }
+                }
+
+                const selfClosing = source[end - 1] === "/";
+                const tag = source.slice(start + 1, selfClosing ? end - 1 : end);
+                const name = tag.match(/^[^\s\/>]*/)[0];
+                const element = parent.appendChild(new XmlNode(1, name));
+                for (const match of tag.slice(name.length).matchAll(attributePattern)) {
+                    const value = match[2] !== undefined ? match[2] : match[3];
+                    element.attributes[match[1]] = decodeEntities(value);
+                }
+                if (!selfClosing) {
+                    parent = element;
+                }
+                pos = end + 1;
+            }
+        }
+
+        return document;
+    }
+
+    globalThis.DOMParser = class DOMParser {
+        parseFromString(source) {
+            return parseXml(source);
+        }
+    };
+
     globalThis.global = {};
     globalThis.setTimeout = () => { };
     globalThis.clearTimeout = () => { };
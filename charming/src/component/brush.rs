@@ -1 +1,136 @@
+use super::BrushType;
+use crate::{
+    datatype::CompositeValue,
+    element::{Color, Symbol},
+};
+use charming_macros::CharmingSetters;
+use serde::{Deserialize, Serialize};
 
+/// Shape of the areas drawn by the [`Brush`] component. Unlike [`BrushType`],
+/// it has no toolbox-only buttons.
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum BrushSelectType {
+    /// Select a rectangle.
+    Rect,
+    /// Select an arbitrary shape, like a lasso.
+    Polygon,
+    /// Select a range along the x axis.
+    LineX,
+    /// Select a range along the y axis.
+    LineY,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum BrushMode {
+    /// Only one area can be selected at a time.
+    Single,
+    /// Several areas can be selected at once.
+    Multiple,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum BrushThrottleType {
+    /// Trigger the selection event at most once every throttle delay.
+    FixRate,
+    /// Trigger the selection event once the selection has not changed for the
+    /// throttle delay.
+    Debounce,
+}
+
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, PartialOrd, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BrushStyle {
+    border_width: Option<f64>,
+    color: Option<Color>,
+    border_color: Option<Color>,
+}
+
+/// Visual encoding of the data items inside or outside of the selection.
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BrushVisual {
+    symbol: Option<Symbol>,
+    symbol_size: Option<f64>,
+    #[charming_set_vec]
+    color: Vec<Color>,
+    color_alpha: Option<f64>,
+    opacity: Option<f64>,
+    color_lightness: Option<f64>,
+    color_saturation: Option<f64>,
+    color_hue: Option<f64>,
+}
+
+/// Brush component, which selects data items by drawing areas on the chart,
+/// for example lasso selection of the points of a scatter chart.
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Brush {
+    /// Component ID.
+    id: Option<String>,
+
+    /// Buttons shown in the toolbox to select the brush type.
+    #[charming_set_vec]
+    toolbox: Vec<BrushType>,
+
+    /// Series whose selections are linked, `"all"`, `"none"` or an array of
+    /// series indices.
+    brush_link: Option<CompositeValue>,
+
+    /// Series that can be selected, `"all"`, a series index or an array of
+    /// series indices.
+    series_index: Option<CompositeValue>,
+
+    /// Geo components the brush works on.
+    geo_index: Option<CompositeValue>,
+
+    /// X axes the brush works on.
+    x_axis_index: Option<CompositeValue>,
+
+    /// Y axes the brush works on.
+    y_axis_index: Option<CompositeValue>,
+
+    /// Default brush type.
+    brush_type: Option<BrushSelectType>,
+
+    /// Default brush mode.
+    brush_mode: Option<BrushMode>,
+
+    /// Whether the selected areas can be moved and resized.
+    transformable: Option<bool>,
+
+    /// Style of the selected areas.
+    brush_style: Option<BrushStyle>,
+
+    /// How often the selection event is triggered while brushing.
+    throttle_type: Option<BrushThrottleType>,
+
+    /// Delay of the throttle in milliseconds.
+    throttle_delay: Option<f64>,
+
+    /// Whether clicking on the chart removes all selected areas.
+    remove_on_click: Option<bool>,
+
+    /// Visual encoding of the selected data items.
+    in_brush: Option<BrushVisual>,
+
+    /// Visual encoding of the data items that are not selected.
+    out_of_brush: Option<BrushVisual>,
+
+    /// Z value of the selected areas.
+    z: Option<f64>,
+}
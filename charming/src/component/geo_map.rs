@@ -45,3 +45,47 @@ impl From<(&str, &str)> for GeoMap {
             .opt(GeoMapOpt::Svg(svg.to_string()))
     }
 }
+
+impl GeoMapOpt {
+    /// The `opt` argument of `echarts.registerMap`.
+    #[cfg(any(feature = "html", feature = "wasm"))]
+    pub(crate) fn to_register_opt(&self) -> serde_json::Value {
+        match self {
+            GeoMapOpt::GeoJson {
+                value,
+                special_areas,
+            } => {
+                let mut opt = serde_json::json!({ "geoJSON": value });
+                if !special_areas.is_null() {
+                    opt["specialAreas"] = special_areas.clone();
+                }
+                opt
+            }
+            GeoMapOpt::Svg(svg) => serde_json::json!({ "svg": svg }),
+        }
+    }
+}
+
+impl GeoMap {
+    /// The name and `opt` arguments of `echarts.registerMap`. Maps without a
+    /// name or without data cannot be registered and yield `None`.
+    #[cfg(any(feature = "html", feature = "wasm"))]
+    pub(crate) fn register_args(&self) -> Option<(&str, serde_json::Value)> {
+        match (&self.name, &self.opt) {
+            (Some(name), Some(opt)) => Some((name, opt.to_register_opt())),
+            _ => None,
+        }
+    }
+
+    /// The `echarts.registerMap(name, opt);` statement for this map.
+    #[cfg(feature = "html")]
+    pub(crate) fn register_script(&self) -> Option<String> {
+        self.register_args().map(|(name, opt)| {
+            format!(
+                "echarts.registerMap({}, {});",
+                serde_json::Value::from(name),
+                opt
+            )
+        })
+    }
+}
@@ -1 +1,236 @@
+use crate::{
+    datatype::CompositeValue,
+    element::{Color, JsFunction, TextAlign, TextVerticalAlign},
+};
+use charming_macros::CharmingSetters;
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum GraphicType {
+    /// A container of other elements, which are positioned together.
+    Group,
+    Image,
+    Text,
+    Rect,
+    Circle,
+    Ring,
+    Sector,
+    Arc,
+    Polygon,
+    Polyline,
+    Line,
+    BezierCurve,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum GraphicAction {
+    /// Replace the existing element with the same id.
+    Replace,
+    /// Merge into the existing element with the same id.
+    Merge,
+    /// Remove the existing element with the same id.
+    Remove,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum GraphicBounding {
+    /// Position the element by its own bounding box and the ones of its
+    /// children.
+    All,
+    /// Position the element by its own bounding box only.
+    Raw,
+}
+
+/// Geometry of a graphic element. Which fields apply depends on the type of
+/// the element:
+///
+/// - `rect`: `x`, `y`, `width`, `height` and `r` for rounded corners.
+/// - `circle`: `cx`, `cy` and `r`.
+/// - `ring`: `cx`, `cy`, `r` and `r0`.
+/// - `sector` and `arc`: `cx`, `cy`, `r`, `r0`, `start_angle`, `end_angle`
+///   and `clockwise`.
+/// - `polygon` and `polyline`: `points`, `smooth` and `smooth_constraint`.
+/// - `line`: `x1`, `y1`, `x2`, `y2` and `percent`.
+/// - `bezierCurve`: `x1`, `y1`, `x2`, `y2`, `cpx1`, `cpy1`, `cpx2`, `cpy2`
+///   and `percent`.
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, PartialOrd, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphicShape {
+    x: Option<f64>,
+    y: Option<f64>,
+    width: Option<f64>,
+    height: Option<f64>,
+    /// Radius, or the corner radius of a rect, either one number or an array
+    /// of four numbers.
+    r: Option<CompositeValue>,
+    /// Inner radius.
+    r0: Option<f64>,
+    cx: Option<f64>,
+    cy: Option<f64>,
+    start_angle: Option<f64>,
+    end_angle: Option<f64>,
+    clockwise: Option<bool>,
+    #[charming_set_vec]
+    points: Vec<CompositeValue>,
+    smooth: Option<CompositeValue>,
+    smooth_constraint: Option<bool>,
+    x1: Option<f64>,
+    y1: Option<f64>,
+    x2: Option<f64>,
+    y2: Option<f64>,
+    cpx1: Option<f64>,
+    cpy1: Option<f64>,
+    cpx2: Option<f64>,
+    cpy2: Option<f64>,
+    /// How much of a line or a curve is drawn, from 0 to 1.
+    percent: Option<f64>,
+}
+
+/// Style of a graphic element. `image`, `x`, `y`, `width` and `height` are
+/// for `image` elements, `text`, `font`, `text_align` and
+/// `text_vertical_align` for `text` elements.
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, PartialOrd, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphicStyle {
+    fill: Option<Color>,
+    stroke: Option<Color>,
+    line_width: Option<f64>,
+    #[charming_set_vec]
+    line_dash: Vec<f64>,
+    opacity: Option<f64>,
+    shadow_blur: Option<f64>,
+    shadow_offset_x: Option<f64>,
+    shadow_offset_y: Option<f64>,
+    shadow_color: Option<Color>,
+    /// URL or data URL of the image.
+    image: Option<String>,
+    x: Option<f64>,
+    y: Option<f64>,
+    width: Option<f64>,
+    height: Option<f64>,
+    text: Option<String>,
+    /// CSS font, e.g. `bold 16px sans-serif`.
+    font: Option<String>,
+    text_align: Option<TextAlign>,
+    text_vertical_align: Option<TextVerticalAlign>,
+}
+
+/// An element of the [`Graphic`] component.
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphicElement {
+    #[serde(rename = "type")]
+    type_: Option<GraphicType>,
+
+    /// Element ID, which is used to update the element later.
+    id: Option<String>,
+
+    /// What to do with an existing element with the same id.
+    #[serde(rename = "$action")]
+    action: Option<GraphicAction>,
+
+    /// Distance between the element and the left side of its container.
+    left: Option<CompositeValue>,
+
+    /// Distance between the element and the top side of its container.
+    top: Option<CompositeValue>,
+
+    /// Distance between the element and the right side of its container.
+    right: Option<CompositeValue>,
+
+    /// Distance between the element and the bottom side of its container.
+    bottom: Option<CompositeValue>,
+
+    bounding: Option<GraphicBounding>,
+
+    /// Width of a group, used to position its children.
+    width: Option<f64>,
+
+    /// Height of a group, used to position its children.
+    height: Option<f64>,
+
+    x: Option<f64>,
+    y: Option<f64>,
+
+    /// Rotation in radians.
+    rotation: Option<f64>,
+
+    scale_x: Option<f64>,
+    scale_y: Option<f64>,
+
+    /// X coordinate of the origin of rotation and scaling.
+    origin_x: Option<f64>,
+
+    /// Y coordinate of the origin of rotation and scaling.
+    origin_y: Option<f64>,
+
+    z: Option<f64>,
+    zlevel: Option<f64>,
+
+    /// Whether the element ignores mouse events.
+    silent: Option<bool>,
+
+    /// Whether the element is hidden.
+    invisible: Option<bool>,
+
+    /// Cursor style when hovering the element, e.g. `pointer` or `move`.
+    cursor: Option<String>,
+
+    /// Whether the element can be dragged.
+    draggable: Option<bool>,
+
+    shape: Option<GraphicShape>,
+    style: Option<GraphicStyle>,
+
+    /// Children of a group.
+    #[charming_set_vec]
+    children: Vec<GraphicElement>,
+
+    onclick: Option<JsFunction>,
+    onmouseover: Option<JsFunction>,
+    onmouseout: Option<JsFunction>,
+    ondrag: Option<JsFunction>,
+}
+
+/// Graphic component, which draws images, text and shapes on the chart, such
+/// as watermarks, logos or annotations.
+///
+/// ```rust
+/// use charming::component::{Graphic, GraphicElement, GraphicStyle, GraphicType};
+///
+/// let logo = Graphic::new().elements(vec![
+///     GraphicElement::new()
+///         .type_(GraphicType::Text)
+///         .right(20)
+///         .bottom(20)
+///         .style(GraphicStyle::new().text("ACME Corp.").fill("#999")),
+/// ]);
+/// ```
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Graphic {
+    /// Component ID.
+    id: Option<String>,
+
+    #[charming_set_vec]
+    elements: Vec<GraphicElement>,
+}
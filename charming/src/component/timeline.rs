@@ -1 +1,155 @@
+use crate::{
+    datatype::CompositeValue,
+    element::{AxisType, Color, Icon, ItemStyle, Label, LineStyle, Orient, Padding},
+};
+use charming_macros::CharmingSetters;
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum TimelineControlPosition {
+    Left,
+    Right,
+}
+
+/// Style of the point marking the current frame.
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, PartialOrd, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineCheckpointStyle {
+    symbol: Option<Icon>,
+    symbol_size: Option<f64>,
+    color: Option<Color>,
+    border_width: Option<f64>,
+    border_color: Option<Color>,
+    /// Whether the checkpoint moves with an animation.
+    animation: Option<bool>,
+    animation_duration: Option<f64>,
+}
+
+/// Style of the play, previous and next buttons.
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, PartialOrd, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineControlStyle {
+    show: Option<bool>,
+    show_play_btn: Option<bool>,
+    show_prev_btn: Option<bool>,
+    show_next_btn: Option<bool>,
+    item_size: Option<f64>,
+    item_gap: Option<f64>,
+    position: Option<TimelineControlPosition>,
+    play_icon: Option<Icon>,
+    stop_icon: Option<Icon>,
+    prev_icon: Option<Icon>,
+    next_icon: Option<Icon>,
+    color: Option<Color>,
+    border_color: Option<Color>,
+    border_width: Option<f64>,
+}
+
+/// Timeline component, which switches between the frames of a chart given
+/// with [`Chart::options`](crate::Chart::options), and can play them as an
+/// animation.
+///
+/// Every frame is a partial chart, which is merged into the chart itself. The
+/// chart holds what all frames share, like axes and the series types, while
+/// the frames hold what changes, like titles and data.
+///
+/// ```rust
+/// use charming::{
+///     Chart,
+///     component::{Timeline, Title},
+///     element::AxisType,
+///     series::Bar,
+/// };
+///
+/// let chart = Chart::new()
+///     .timeline(
+///         Timeline::new()
+///             .axis_type(AxisType::Category)
+///             .auto_play(true)
+///             .data(vec!["2023", "2024"]),
+///     )
+///     .series(Bar::new())
+///     .options(vec![
+///         Chart::new()
+///             .title(Title::new().text("GDP in 2023"))
+///             .series(Bar::new().data(vec![120, 200])),
+///         Chart::new()
+///             .title(Title::new().text("GDP in 2024"))
+///             .series(Bar::new().data(vec![130, 210])),
+///     ]);
+/// ```
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Timeline {
+    /// Whether to show the component.
+    show: Option<bool>,
+
+    /// Type of the timeline axis.
+    axis_type: Option<AxisType>,
+
+    /// Index of the frame shown first.
+    current_index: Option<f64>,
+
+    /// Whether to start playing the frames automatically.
+    auto_play: Option<bool>,
+
+    /// Whether to play the frames backwards after the last one.
+    rewind: Option<bool>,
+
+    /// Whether to start again after the last frame.
+    #[serde(rename = "loop")]
+    loop_: Option<bool>,
+
+    /// Time in milliseconds between two frames while playing.
+    play_interval: Option<f64>,
+
+    /// Whether to update the chart while dragging the timeline.
+    realtime: Option<bool>,
+
+    /// Position of the play, previous and next buttons.
+    control_position: Option<TimelineControlPosition>,
+
+    z: Option<f64>,
+    zlevel: Option<f64>,
+
+    /// Distance between the component and the left side of the container.
+    left: Option<CompositeValue>,
+
+    /// Distance between the component and the top side of the container.
+    top: Option<CompositeValue>,
+
+    /// Distance between the component and the right side of the container.
+    right: Option<CompositeValue>,
+
+    /// Distance between the component and the bottom side of the container.
+    bottom: Option<CompositeValue>,
+
+    padding: Option<Padding>,
+    orient: Option<Orient>,
+    inverse: Option<bool>,
+    symbol: Option<Icon>,
+    symbol_size: Option<f64>,
+    line_style: Option<LineStyle>,
+    label: Option<Label>,
+    item_style: Option<ItemStyle>,
+    checkpoint_style: Option<TimelineCheckpointStyle>,
+    control_style: Option<TimelineControlStyle>,
+
+    /// Labels of the frames, one for each of
+    /// [`Chart::options`](crate::Chart::options).
+    #[charming_set_vec]
+    data: Vec<CompositeValue>,
+}
@@ -83,11 +83,17 @@ pub struct MagicType {
 #[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 pub enum BrushType {
+    /// Select a rectangle.
     Rect,
+    /// Select an arbitrary shape, like a lasso.
     Polygon,
+    /// Select a range along the x axis.
     LineX,
+    /// Select a range along the y axis.
     LineY,
+    /// Toolbox button switching between single and multiple selections.
     Keep,
+    /// Toolbox button removing all selections.
     Clear,
 }
 
@@ -97,12 +103,17 @@ pub enum BrushType {
 )]
 #[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, PartialOrd, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct Brush {
+pub struct ToolboxBrush {
     #[serde(rename = "type")]
     #[charming_set_vec]
     type_: Vec<BrushType>,
 }
 
+/// The brush feature of the toolbox, which was renamed to [`ToolboxBrush`]
+/// when the [`Brush`](super::Brush) component was added.
+#[deprecated(note = "use `ToolboxBrush` instead")]
+pub type Brush = ToolboxBrush;
+
 #[serde_with::apply(
   Option => #[serde(skip_serializing_if = "Option::is_none")],
   Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -125,7 +136,7 @@ pub struct Feature {
     data_view: Option<DataView>,
     magic_type: Option<MagicType>,
     data_zoom: Option<ToolboxDataZoom>,
-    brush: Option<Brush>,
+    brush: Option<ToolboxBrush>,
 }
 
 #[serde_with::apply(
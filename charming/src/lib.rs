@@ -95,9 +95,9 @@ pub use renderer::*;
 
 use charming_macros::CharmingSetters;
 use component::{
-    AngleAxis, Aria, Axis, Axis3D, Calendar, DataZoom, GeoMap, Grid, Grid3D, LegendConfig,
-    ParallelAxis, ParallelCoordinate, PolarCoordinate, RadarCoordinate, RadiusAxis,
-    SaveAsImageType, SingleAxis, Title, Toolbox, VisualMap,
+    AngleAxis, Aria, Axis, Axis3D, Brush, Calendar, DataZoom, Geo, GeoMap, Graphic, Grid, Grid3D,
+    LegendConfig, ParallelAxis, ParallelCoordinate, PolarCoordinate, RadarCoordinate, RadiusAxis,
+    SaveAsImageType, SingleAxis, Timeline, Title, Toolbox, VisualMap,
 };
 use datatype::Dataset;
 use element::{AnimationTime, AxisPointer, Color, Easing, MarkLine, Tooltip, process_raw_strings};
@@ -224,6 +224,11 @@ data in different scales. A chart can have multiple data zooms.
 such as color, symbol size or symbol shape. A chart can have multiple visual
 maps.
 
+### Brush
+
+[`Brush`] selects data items by drawing rectangles, polygons or ranges on the
+chart, for example to lasso-select the points of a scatter chart.
+
 ### Tooltip
 
 [`Tooltip`] is a floating box that appears when user hovers over a data item.
@@ -233,6 +238,16 @@ maps.
 [`AxisPointer`] is a tool for displaying reference line and axis value under
 mouse pointer.
 
+### Graphic
+
+[`Graphic`] draws images, text and shapes on top of the chart, such as
+watermarks, logos and annotations.
+
+### Timeline
+
+[`Timeline`] switches between frames of the chart, which are given with
+[`Chart::options`], and can play them as an animation.
+
 ### Toolbox
 
 [`Toolbox`] is a feature toolbox that includes data view, save as image, data
@@ -279,8 +294,10 @@ pub struct Chart {
     axis_pointer: Vec<AxisPointer>,
     visual_map: Vec<VisualMap>,
     data_zoom: Vec<DataZoom>,
+    brush: Option<Brush>,
     parallel: Option<ParallelCoordinate>,
     calendar: Option<Calendar>,
+    geo: Vec<Geo>,
     dataset: Option<Dataset>,
     radar: Vec<RadarCoordinate>,
     #[charming_set_vec]
@@ -288,11 +305,41 @@ pub struct Chart {
     background_color: Option<Color>,
     mark_line: Option<MarkLine>,
     aria: Option<Aria>,
+    graphic: Option<Graphic>,
     series: Vec<Series>,
+    timeline: Option<Timeline>,
+    /// Frames of the [`Timeline`], which are merged into this chart one at a
+    /// time.
+    #[charming_set_vec]
+    options: Vec<Chart>,
     #[serde(skip_serializing)]
     geo_map: Vec<GeoMap>,
 }
 impl Chart {
+    /// Serialize the chart into the option passed to echarts' `setOption`,
+    /// the same as its [`Display`](std::fmt::Display) output. Functions given
+    /// as raw strings are written as JavaScript code rather than as strings.
+    pub fn to_json(&self) -> Result<String, EchartsError> {
+        Ok(process_raw_strings(&serde_json::to_string_pretty(self)?))
+    }
+
+    /// The `echarts.registerMap` statements for all the [`GeoMap`]s of this
+    /// chart, which have to run before the chart option is set.
+    #[cfg(feature = "html")]
+    pub(crate) fn register_map_scripts(&self) -> Vec<String> {
+        self.geo_maps()
+            .filter_map(|geo_map| geo_map.register_script())
+            .collect()
+    }
+
+    /// The [`GeoMap`]s of this chart and of its timeline frames.
+    #[cfg(any(feature = "html", feature = "wasm"))]
+    pub(crate) fn geo_maps(&self) -> impl Iterator<Item = &GeoMap> {
+        self.geo_map
+            .iter()
+            .chain(self.options.iter().flat_map(|option| option.geo_map.iter()))
+    }
+
     pub fn save_as_image_type(&self) -> Option<&SaveAsImageType> {
         self.toolbox
             .as_ref()
@@ -300,13 +347,15 @@ impl Chart {
     }
 }
 
+/// Writes the chart as JSON, the same as [`Chart::to_json`].
+///
+/// Formatting fails with [`std::fmt::Error`] if the chart cannot be
+/// serialized, which makes `chart.to_string()` panic. Use [`Chart::to_json`]
+/// to handle the error instead.
 impl std::fmt::Display for Chart {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            process_raw_strings(&serde_json::to_string_pretty(self).unwrap())
-        )
+        let json = self.to_json().map_err(|_| std::fmt::Error)?;
+        write!(f, "{json}")
     }
 }
 
@@ -316,9 +365,62 @@ pub enum EchartsError {
     ImageRenderingError(String),
     JsRuntimeError(String),
     WasmError(String),
+    /// Reading or writing a file failed.
+    IoError(std::io::Error),
+    /// A template could not be parsed or rendered.
+    TemplateError(Box<dyn std::error::Error + Send + Sync>),
+    /// A chart or an option could not be serialized.
+    SerializationError(serde_json::Error),
+    /// JavaScript threw an exception while rendering the chart.
+    JsException(Box<JsException>),
+    /// The size of an image is zero, negative or too large.
+    InvalidSizeError(String),
+    /// A font could not be loaded.
+    FontError(String),
+    /// Rendering the chart took longer than the given timeout.
+    TimeoutError(std::time::Duration),
+    /// The JavaScript heap grew beyond its limit while rendering the chart.
+    HeapLimitError(String),
+    /// The render was cancelled from another thread, e.g. because the render
+    /// job of a pool was dropped.
+    Cancelled,
+    /// The renderer stopped before finishing the render, e.g. because
+    /// creating it panicked.
+    RendererStopped,
+    /// Several charts of a page have the same id.
+    DuplicateIdError(String),
+    /// The id of a chart is empty or contains whitespace, which HTML does
+    /// not allow.
+    InvalidIdError(String),
+    /// The JavaScript runtime could not be created, e.g. from a broken
+    /// snapshot.
+    RuntimeCreationError(Box<dyn std::error::Error + Send + Sync>),
+    /// The render script did not evaluate to a string.
+    ResultConversionError(Box<dyn std::error::Error + Send + Sync>),
+    /// The SVG of a chart could not be parsed for rasterizing.
+    SvgParseError(Box<dyn std::error::Error + Send + Sync>),
+    /// An image could not be encoded.
+    ImageEncodingError(Box<dyn std::error::Error + Send + Sync>),
+    /// The SVG of a chart could not be converted to PDF.
+    PdfError(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl std::error::Error for EchartsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::IoError(error) => Some(error),
+            Self::TemplateError(error)
+            | Self::RuntimeCreationError(error)
+            | Self::ResultConversionError(error)
+            | Self::SvgParseError(error)
+            | Self::ImageEncodingError(error)
+            | Self::PdfError(error) => Some(error.as_ref()),
+            Self::SerializationError(error) => Some(error),
+            _ => None,
+        }
+    }
 }
 
-impl std::error::Error for EchartsError {}
 impl std::fmt::Display for EchartsError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -326,6 +428,67 @@ impl std::fmt::Display for EchartsError {
             Self::ImageRenderingError(msg) => write!(f, "Image rendering error: {msg}"),
             Self::JsRuntimeError(msg) => write!(f, "JavaScript runtime error: {msg}"),
             Self::WasmError(msg) => write!(f, "WebAssembly runtime error: {msg}"),
+            Self::IoError(error) => write!(f, "I/O error: {error}"),
+            Self::TemplateError(error) => write!(f, "Template error: {error}"),
+            Self::SerializationError(error) => write!(f, "Serialization error: {error}"),
+            Self::JsException(exception) => write!(f, "JavaScript exception: {exception}"),
+            Self::InvalidSizeError(msg) => write!(f, "Invalid size: {msg}"),
+            Self::FontError(msg) => write!(f, "Font error: {msg}"),
+            Self::TimeoutError(timeout) => {
+                write!(f, "Timeout error: rendering took longer than {timeout:?}")
+            }
+            Self::HeapLimitError(msg) => write!(f, "Heap limit error: {msg}"),
+            Self::Cancelled => write!(f, "The render was cancelled"),
+            Self::RendererStopped => {
+                write!(f, "The renderer stopped before finishing the render")
+            }
+            Self::DuplicateIdError(id) => write!(f, "Duplicate chart id: {id}"),
+            Self::InvalidIdError(id) => write!(f, "Invalid chart id: {id:?}"),
+            Self::RuntimeCreationError(error) => write!(f, "Runtime creation error: {error}"),
+            Self::ResultConversionError(error) => write!(f, "Result conversion error: {error}"),
+            Self::SvgParseError(error) => write!(f, "SVG parse error: {error}"),
+            Self::ImageEncodingError(error) => write!(f, "Image encoding error: {error}"),
+            Self::PdfError(error) => write!(f, "PDF error: {error}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for EchartsError {
+    fn from(error: std::io::Error) -> Self {
+        Self::IoError(error)
+    }
+}
+
+impl From<serde_json::Error> for EchartsError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::SerializationError(error)
+    }
+}
+
+/// An exception thrown by JavaScript, with the location it was thrown from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsException {
+    /// The message of the exception, e.g. `TypeError: x is not a function`.
+    pub message: String,
+    /// The stack trace of the exception, if it has one.
+    pub stack: Option<String>,
+    /// The line of source code the exception was thrown from.
+    pub source_line: Option<String>,
+    /// The 1-based line of the script the exception was thrown from.
+    pub line_number: Option<i64>,
+    /// The 1-based column in that line the exception was thrown from.
+    pub column_number: Option<i64>,
+}
+
+impl std::fmt::Display for JsException {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let (Some(line), Some(column)) = (self.line_number, self.column_number) {
+            write!(f, " at {line}:{column}")?;
+        }
+        if let Some(source_line) = &self.source_line {
+            write!(f, "\n    {}", source_line.trim())?;
         }
+        Ok(())
     }
 }
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+#[cfg(feature = "ssr-raster")]
+use super::image_renderer::ImageFormat;
+use super::{
+    image_renderer::{ImageRenderer, RenderOptions},
+    image_renderer_pool::{ImageRendererPool, RenderJob},
+};
+use crate::Chart;
+
+/// An [`ImageRenderer`] for asynchronous code, e.g. a tokio based web server.
+///
+/// The renderer runs on a dedicated thread, so rendering does not block the
+/// executor. `AsyncImageRenderer` is `Send + Sync` and can be shared, e.g. in
+/// the state of an axum app. Each render returns a [`RenderJob`], which can be
+/// awaited and is cancelled when dropped, which also terminates a render in
+/// progress. For rendering many charts in parallel, use an
+/// [`ImageRendererPool`] instead.
+///
+/// ```no_run
+/// # async fn example() {
+/// use std::time::Duration;
+/// use charming::{AsyncImageRenderer, Chart};
+///
+/// let renderer = AsyncImageRenderer::new(800, 600).timeout(Duration::from_secs(5));
+/// let svg = renderer.render(&Chart::new()).await.unwrap();
+/// # }
+/// ```
+pub struct AsyncImageRenderer {
+    pool: ImageRendererPool,
+}
+
+impl AsyncImageRenderer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::with_renderer(move || ImageRenderer::new(width, height))
+    }
+
+    /// Create the renderer with `factory` on the dedicated thread, e.g. to set
+    /// a theme or load fonts.
+    pub fn with_renderer<F>(factory: F) -> Self
+    where
+        F: Fn() -> ImageRenderer + Send + Sync + 'static,
+    {
+        Self {
+            pool: ImageRendererPool::new(1, factory),
+        }
+    }
+
+    /// Set the time a render may take, including the time it waits for the
+    /// renders submitted before it.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.pool = self.pool.timeout(timeout);
+        self
+    }
+
+    /// Render chart to an SVG String.
+    pub fn render(&self, chart: &Chart) -> RenderJob<String> {
+        self.pool.render_async(chart)
+    }
+
+    /// Render chart to an SVG String, overriding the settings of the renderer
+    /// with `options`.
+    pub fn render_with(&self, chart: &Chart, options: &RenderOptions) -> RenderJob<String> {
+        let chart = chart.clone();
+        let options = options.clone();
+        self.pool
+            .submit(move |renderer| renderer.render_with(&chart, &options))
+    }
+
+    /// Render a chart to a given image format in bytes.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn render_format(&self, image_format: ImageFormat, chart: &Chart) -> RenderJob<Vec<u8>> {
+        self.pool.render_format_async(image_format, chart)
+    }
+
+    /// Render a chart to a given image format in bytes, overriding the
+    /// settings of the renderer with `options`.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn render_format_with(
+        &self,
+        image_format: ImageFormat,
+        chart: &Chart,
+        options: &RenderOptions,
+    ) -> RenderJob<Vec<u8>> {
+        let chart = chart.clone();
+        let options = options.clone();
+        self.pool
+            .submit(move |renderer| renderer.render_format_with(image_format, &chart, &options))
+    }
+}
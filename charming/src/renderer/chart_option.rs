@@ -0,0 +1,320 @@
+//! Helpers to read the echarts option of a chart, shared by the renderers
+//! that lay out charts in Rust.
+
+use serde_json::Value;
+
+use crate::{Chart, EchartsError, element::RAW_MARK};
+
+pub(crate) static PALETTE: [&str; 9] = [
+    "#5470c6", "#91cc75", "#fac858", "#ee6666", "#73c0de", "#3ba272", "#fc8452", "#9a60b4",
+    "#ea7ccc",
+];
+
+/// The most ticks a value axis gets, whatever its `interval`.
+const MAX_TICKS: f64 = 1000.0;
+
+/// The option of a chart as echarts shows it at first, which for a chart
+/// with a timeline is the chart merged with the frame at the current index.
+pub(crate) fn chart_option(chart: &Chart) -> Result<Value, EchartsError> {
+    let mut option = serde_json::to_value(chart)?;
+    let Some(frames) = option
+        .as_object_mut()
+        .and_then(|option| option.remove("options"))
+    else {
+        return Ok(option);
+    };
+    let index = option["timeline"]["currentIndex"].as_f64().unwrap_or(0.0) as usize;
+    if let Some(frame) = all(&frames).get(index) {
+        merge(&mut option, frame);
+    }
+    Ok(option)
+}
+
+/// Merges a partial option into an option like `setOption` does: objects are
+/// merged field by field and components by index, while data and other
+/// values are replaced.
+fn merge(target: &mut Value, source: &Value) {
+    let is_components = |value: &Value| all(value).iter().all(|item| item.is_object());
+    match (target, source) {
+        (Value::Object(target), Value::Object(source)) => {
+            for (key, value) in source {
+                match target.get_mut(key) {
+                    Some(existing) if key != "data" => merge(existing, value),
+                    _ => {
+                        target.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, source)
+            if (target.is_array() || source.is_array())
+                && is_components(target)
+                && is_components(source) =>
+        {
+            let mut items: Vec<Value> = all(target).into_iter().cloned().collect();
+            for (index, value) in all(source).into_iter().enumerate() {
+                match items.get_mut(index) {
+                    Some(item) => merge(item, value),
+                    None => items.push(value.clone()),
+                }
+            }
+            *target = Value::Array(items);
+        }
+        (target, source) => *target = source.clone(),
+    }
+}
+
+/// The labels of a category axis, or `None` for other axes.
+pub(crate) fn category_labels(axis: &Value) -> Option<Vec<String>> {
+    let is_category = match text(&axis["type"]) {
+        Some(type_) => type_ == "category",
+        None => axis["data"].is_array(),
+    };
+    is_category.then(|| {
+        all(&axis["data"])
+            .into_iter()
+            .map(|item| match item_value(item) {
+                Value::String(label) => label.clone(),
+                value => number(value).map(num).unwrap_or_default(),
+            })
+            .collect()
+    })
+}
+
+/// The number of intervals between the ticks of a value axis, at most
+/// [`MAX_TICKS`].
+pub(crate) fn tick_count(min: f64, max: f64, interval: f64) -> usize {
+    ((max - min) / interval).round().clamp(0.0, MAX_TICKS) as usize
+}
+
+/// The number of categories of a category axis, extended past its labels to
+/// cover points given by index, e.g. for an axis without `data`. Indices
+/// beyond `items`, the number of data items of the longest series, do not
+/// count, so a huge index does not add a category for every index below it.
+pub(crate) fn category_count(
+    labels: &[String],
+    indices: impl Iterator<Item = f64>,
+    items: usize,
+) -> usize {
+    let limit = labels.len().max(items) as f64;
+    indices
+        .map(f64::round)
+        .filter(|index| (0.0..limit).contains(index))
+        .map(|index| index as usize + 1)
+        .max()
+        .unwrap_or(0)
+        .max(labels.len())
+}
+
+/// Whether a position lies on an axis with the given category labels, which
+/// is always the case for other axes.
+pub(crate) fn in_categories(labels: Option<&[String]>, position: f64) -> bool {
+    labels.is_none_or(|labels| (0.0..labels.len() as f64).contains(&position.round()))
+}
+
+/// The `(min, max, interval)` of a value axis covering the values, with
+/// round ticks like echarts picks them unless the axis sets `min`, `max` or
+/// `interval`.
+pub(crate) fn value_extent(axis: &Value, values: impl Iterator<Item = f64>) -> (f64, f64, f64) {
+    let (mut min, mut max) = values
+        .filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+    if axis["scale"] != true {
+        min = min.min(0.0);
+        max = max.max(0.0);
+    }
+    if !min.is_finite() {
+        (min, max) = (0.0, 1.0);
+    }
+    let fixed_min = axis["min"].as_f64();
+    let fixed_max = axis["max"].as_f64();
+    min = fixed_min.unwrap_or(min);
+    max = fixed_max.unwrap_or(max);
+    if min > max {
+        (min, max) = (max, min);
+    }
+    if min == max {
+        if min == 0.0 {
+            max = 1.0;
+        } else {
+            let margin = min.abs() / 2.0;
+            (min, max) = (min - margin, max + margin);
+        }
+    }
+
+    // An interval that is not positive or gives too many ticks is ignored.
+    let split_number = axis["splitNumber"]
+        .as_f64()
+        .filter(|split_number| *split_number >= 1.0)
+        .unwrap_or(5.0);
+    let interval = axis["interval"]
+        .as_f64()
+        .filter(|interval| *interval > 0.0 && (max - min) / interval <= MAX_TICKS)
+        .unwrap_or_else(|| nice((max - min) / split_number));
+    let precision = precision(interval) as i32;
+    let round = |value: f64| {
+        let factor = 10f64.powi(precision);
+        (value * factor).round() / factor
+    };
+    if fixed_min.is_none() {
+        min = round((min / interval).floor() * interval);
+    }
+    if fixed_max.is_none() {
+        max = round((max / interval).ceil() * interval);
+    }
+    (min, max, interval)
+}
+
+/// Rounds an interval to 1, 2, 3, 5 or 10 times a power of ten.
+pub(crate) fn nice(value: f64) -> f64 {
+    let mut exponent = value.log10().floor();
+    if value / 10f64.powf(exponent) >= 10.0 {
+        exponent += 1.0;
+    }
+    let exp10 = 10f64.powf(exponent);
+    let fraction = value / exp10;
+    let nice = if fraction < 1.5 {
+        1.0
+    } else if fraction < 2.5 {
+        2.0
+    } else if fraction < 4.0 {
+        3.0
+    } else if fraction < 7.0 {
+        5.0
+    } else {
+        10.0
+    };
+    let factor = 10f64.powf(-exponent.min(0.0));
+    (nice * exp10 * factor).round() / factor
+}
+
+/// Number of decimal places of a value.
+pub(crate) fn precision(value: f64) -> usize {
+    let value = num(value);
+    value.find('.').map_or(0, |dot| value.len() - dot - 1)
+}
+
+/// Formats a number for a label, with thousands separators.
+pub(crate) fn format_number(value: f64, precision: usize) -> String {
+    let formatted = format!("{value:.precision$}");
+    let (integer, fraction) = formatted
+        .split_once('.')
+        .map_or((formatted.as_str(), None), |(integer, fraction)| {
+            (integer, Some(fraction))
+        });
+    let (sign, digits) = match integer.strip_prefix('-') {
+        Some(digits) if digits.chars().any(|c| c != '0') || fraction.is_some() => ("-", digits),
+        Some(digits) => ("", digits),
+        None => ("", integer),
+    };
+    let mut grouped = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    match fraction {
+        Some(fraction) => format!("{sign}{grouped}.{fraction}"),
+        None => format!("{sign}{grouped}"),
+    }
+}
+
+/// The position of a value on an axis, category names are looked up in the
+/// labels of category axes.
+pub(crate) fn coordinate(labels: Option<&[String]>, value: &Value) -> Option<f64> {
+    match (labels, value) {
+        (Some(labels), Value::String(label)) => labels
+            .iter()
+            .position(|candidate| candidate == label)
+            .map(|index| index as f64),
+        _ => number(value),
+    }
+}
+
+/// The items of an option that is either a single object or an array.
+pub(crate) fn all(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Null => Vec::new(),
+        item => vec![item],
+    }
+}
+
+pub(crate) fn first(value: &Value) -> &Value {
+    match value {
+        Value::Array(items) => items.first().unwrap_or(&Value::Null),
+        item => item,
+    }
+}
+
+/// The value of a data item, which is either given directly or in an
+/// object with a `value` field.
+pub(crate) fn item_value(item: &Value) -> &Value {
+    match item {
+        Value::Object(object) => object.get("value").unwrap_or(&Value::Null),
+        value => value,
+    }
+}
+
+/// A string option, except for JavaScript functions.
+pub(crate) fn text(value: &Value) -> Option<&str> {
+    value.as_str().filter(|value| !value.starts_with(RAW_MARK))
+}
+
+pub(crate) fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(number) => number.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// A length given in pixels or as a percentage of `total`.
+#[cfg(feature = "native-svg")]
+pub(crate) fn length(value: &Value, total: f64) -> Option<f64> {
+    match value {
+        Value::String(length) => match length.trim().strip_suffix('%') {
+            Some(percent) => percent
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .map(|p| p / 100.0 * total),
+            None => length.trim().parse().ok(),
+        },
+        value => value.as_f64(),
+    }
+}
+
+/// Formats a coordinate with at most four decimal places.
+pub(crate) fn num(value: f64) -> String {
+    let rounded = (value * 1e4).round() / 1e4;
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        rounded.to_string()
+    }
+}
+
+/// The red, green and blue channels of a `#rgb` or `#rrggbb` color.
+pub(crate) fn parse_color(color: &str) -> Option<[f64; 3]> {
+    let hex = color.strip_prefix('#')?;
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok().map(f64::from);
+    match hex.len() {
+        3 => {
+            let mut channels = hex.chars().map(|c| channel(&c.to_string().repeat(2)));
+            Some([channels.next()??, channels.next()??, channels.next()??])
+        }
+        6 => Some([
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        ]),
+        _ => None,
+    }
+}
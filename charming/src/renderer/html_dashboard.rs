@@ -0,0 +1,148 @@
+use std::collections::BTreeSet;
+
+use crate::{Chart, EchartsError, theme::Theme};
+
+use super::html_renderer::{ScriptSource, chart_data, escape_script, render_template};
+
+/// How the charts of a [`HtmlDashboard`] are arranged on the page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DashboardLayout {
+    /// A CSS grid with the given number of columns.
+    Grid(u32),
+    /// A wrapping CSS flex box, placing as many charts in a row as fit.
+    Flex,
+}
+
+/// A chart placed on a [`HtmlDashboard`], with its own element id and size.
+#[derive(Clone, Debug)]
+pub struct DashboardChart {
+    id: String,
+    chart: Chart,
+    width: u64,
+    height: u64,
+    group: Option<String>,
+}
+
+impl DashboardChart {
+    pub fn new<S: Into<String>>(id: S, chart: Chart, width: u64, height: u64) -> Self {
+        Self {
+            id: id.into(),
+            chart,
+            width,
+            height,
+            group: None,
+        }
+    }
+
+    /// Put the chart into a group. Charts in the same group are connected with
+    /// `echarts.connect`, which synchronizes their tooltips and data zooms.
+    pub fn group<S: Into<String>>(mut self, group: S) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+}
+
+/// Renders many charts into a single HTML page, which loads echarts once and
+/// initializes each chart in its own container.
+pub struct HtmlDashboard {
+    title: String,
+    theme: Theme,
+    script_source: ScriptSource,
+    layout: DashboardLayout,
+    gap: u64,
+    charts: Vec<DashboardChart>,
+}
+
+impl HtmlDashboard {
+    pub fn new<S: Into<String>>(title: S) -> Self {
+        Self {
+            title: title.into(),
+            theme: Theme::Default,
+            script_source: ScriptSource::Cdn,
+            layout: DashboardLayout::Flex,
+            gap: 16,
+            charts: vec![],
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Set where the page loads echarts from, see [`ScriptSource`].
+    pub fn script_source(mut self, script_source: ScriptSource) -> Self {
+        self.script_source = script_source;
+        self
+    }
+
+    pub fn layout(mut self, layout: DashboardLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Set the space between charts in px.
+    pub fn gap(mut self, gap: u64) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn chart(mut self, chart: DashboardChart) -> Self {
+        self.charts.push(chart);
+        self
+    }
+
+    pub fn render(&self) -> Result<String, EchartsError> {
+        let mut ids = BTreeSet::new();
+        let mut groups = BTreeSet::new();
+        let mut charts = Vec::with_capacity(self.charts.len());
+
+        for item in &self.charts {
+            if !ids.insert(item.id.as_str()) {
+                return Err(EchartsError::DuplicateIdError(item.id.clone()));
+            }
+            let mut data = chart_data(
+                &item.id,
+                &item.chart,
+                &self.theme,
+                format!("width: {}px; height: {}px", item.width, item.height),
+            )?;
+            if let Some(group) = &item.group {
+                let group = escape_script(&serde_json::Value::from(group.as_str()).to_string());
+                data["group"] = group.clone().into();
+                groups.insert(group);
+            }
+            charts.push(data);
+        }
+
+        render_template(
+            include_str!("../asset/dashboard.html.hbs"),
+            &serde_json::json!({
+                "title": self.title,
+                "scripts": self.script_source.scripts(&self.theme),
+                "layout_style": self.layout_style(),
+                "charts": charts,
+                "groups": groups,
+            }),
+        )
+    }
+
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), EchartsError> {
+        let html = self.render()?;
+        std::fs::write(path, html).map_err(EchartsError::IoError)
+    }
+
+    fn layout_style(&self) -> String {
+        match self.layout {
+            DashboardLayout::Grid(columns) => format!(
+                "display: grid; grid-template-columns: repeat({}, max-content); gap: {}px; justify-content: center;",
+                columns.max(1),
+                self.gap
+            ),
+            DashboardLayout::Flex => format!(
+                "display: flex; flex-wrap: wrap; gap: {}px; justify-content: center;",
+                self.gap
+            ),
+        }
+    }
+}
@@ -1,12 +1,153 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use handlebars::Handlebars;
 
 use crate::{Chart, EchartsError, component::SaveAsImageType, theme::Theme};
 
+pub(crate) static ECHARTS_CDN: &str =
+    "https://cdn.jsdelivr.net/npm/echarts@5.5.1/dist/echarts.min.js";
+static ECHARTS_GL_CDN: &str =
+    "https://cdn.jsdelivr.net/npm/echarts-gl@2.0.9/dist/echarts-gl.min.js";
+
+/// Where the generated HTML page loads the echarts library from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ScriptSource {
+    /// Load echarts and echarts-gl from the jsDelivr CDN.
+    #[default]
+    Cdn,
+    /// Inline the bundled echarts library into the page, so that the page
+    /// renders without network access. echarts-gl is not bundled, so 3D charts
+    /// are not available in this mode.
+    Inline,
+    /// Load echarts, and optionally echarts-gl, from the given URLs. The URLs
+    /// can be absolute or relative to the page.
+    Url {
+        echarts: String,
+        echarts_gl: Option<String>,
+    },
+}
+
+impl ScriptSource {
+    /// Load echarts from the given URL, without echarts-gl.
+    pub fn url<S: Into<String>>(echarts: S) -> Self {
+        Self::Url {
+            echarts: echarts.into(),
+            echarts_gl: None,
+        }
+    }
+
+    /// Script tags to be placed into the page, each one is either a `src`
+    /// reference or inline JavaScript.
+    pub(crate) fn scripts(&self, theme: &Theme) -> Vec<serde_json::Value> {
+        let mut scripts = match self {
+            Self::Cdn => vec![
+                serde_json::json!({ "src": ECHARTS_CDN }),
+                serde_json::json!({ "src": ECHARTS_GL_CDN }),
+            ],
+            Self::Inline => vec![serde_json::json!({
+                "content": escape_script(include_str!("../asset/echarts-5.5.1.min.js")),
+            })],
+            Self::Url {
+                echarts,
+                echarts_gl,
+            } => {
+                let mut scripts = vec![serde_json::json!({ "src": echarts })];
+                if let Some(echarts_gl) = echarts_gl {
+                    scripts.push(serde_json::json!({ "src": echarts_gl }));
+                }
+                scripts
+            }
+        };
+
+        let (_, theme_source) = theme.to_str();
+        if !theme_source.is_empty() {
+            scripts.push(serde_json::json!({ "content": escape_script(theme_source) }));
+        }
+
+        scripts
+    }
+}
+
+/// Prevents inlined JavaScript from closing the surrounding `<script>` tag,
+/// which HTML does on `</script` in any case.
+pub(crate) fn escape_script(source: &str) -> String {
+    let mut escaped = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(index) = rest.find("</") {
+        let (before, after) = rest.split_at(index);
+        escaped.push_str(before);
+        let closes_script = after
+            .get(2..8)
+            .is_some_and(|tag| tag.eq_ignore_ascii_case("script"));
+        escaped.push_str(if closes_script { "<\\/" } else { "</" });
+        rest = &after[2..];
+    }
+    escaped.push_str(rest);
+    escaped
+}
+
+/// The `echarts.registerMap` statements of a chart, safe to be placed into a
+/// `<script>` tag.
+pub(crate) fn register_maps(chart: &Chart) -> Vec<String> {
+    chart
+        .register_map_scripts()
+        .iter()
+        .map(|script| escape_script(script))
+        .collect()
+}
+
+/// The data of the `chart` partial, which renders the container of a chart,
+/// styled with `style`, and the script initializing it.
+pub(crate) fn chart_data(
+    id: &str,
+    chart: &Chart,
+    theme: &Theme,
+    style: String,
+) -> Result<serde_json::Value, EchartsError> {
+    if id.is_empty() || id.contains(|c: char| c.is_ascii_whitespace()) {
+        return Err(EchartsError::InvalidIdError(id.to_string()));
+    }
+    let (theme, _) = theme.to_str();
+    let canvas_type = match chart.save_as_image_type() {
+        Some(&SaveAsImageType::Svg) => "svg".to_string(),
+        _ => "canvas".to_string(),
+    };
+    Ok(serde_json::json!({
+        "theme": theme,
+        "style": style,
+        "chart_id": id,
+        "element_id": escape_script(&serde_json::Value::from(id).to_string()),
+        "canvas_type": canvas_type,
+        "register_maps": register_maps(chart),
+        "chart_option": escape_script(&chart.to_json()?),
+    }))
+}
+
+/// Renders one of the HTML templates, with the `scripts` and `chart` partials
+/// available.
+pub(crate) fn render_template(
+    template: &str,
+    data: &serde_json::Value,
+) -> Result<String, EchartsError> {
+    let mut handlebars = Handlebars::new();
+    handlebars
+        .register_partial("scripts", include_str!("../asset/scripts.html.hbs"))
+        .map_err(|error| EchartsError::TemplateError(Box::new(error)))?;
+    handlebars
+        .register_partial("chart", include_str!("../asset/chart.html.hbs"))
+        .map_err(|error| EchartsError::TemplateError(Box::new(error)))?;
+    handlebars
+        .render_template(template, data)
+        .map_err(|error| EchartsError::TemplateError(Box::new(error)))
+}
+
 pub struct HtmlRenderer {
     title: String,
     theme: Theme,
     width: u64,
     height: u64,
+    script_source: ScriptSource,
+    responsive: bool,
 }
 
 impl HtmlRenderer {
@@ -16,6 +157,8 @@ impl HtmlRenderer {
             theme: Theme::Default,
             width,
             height,
+            script_source: ScriptSource::Cdn,
+            responsive: false,
         }
     }
 
@@ -24,28 +167,68 @@ impl HtmlRenderer {
         self
     }
 
+    /// Set where the page loads echarts from, see [`ScriptSource`].
+    pub fn script_source(mut self, script_source: ScriptSource) -> Self {
+        self.script_source = script_source;
+        self
+    }
+
+    /// Let the chart fill the width of its parent element instead of using a
+    /// fixed width, and resize it together with the browser window. The
+    /// height follows the width, keeping the aspect ratio of the renderer's
+    /// width and height.
+    pub fn responsive(mut self, responsive: bool) -> Self {
+        self.responsive = responsive;
+        self
+    }
+
     pub fn render(&self, chart: &Chart) -> Result<String, EchartsError> {
-        let template = include_str!("../asset/charts.html.hbs");
-        let (theme, theme_source) = self.theme.to_str();
-        let canvas_type = match chart.save_as_image_type() {
-            Some(&SaveAsImageType::Svg) => "svg".to_string(),
-            _ => "canvas".to_string(),
-        };
-        let data = Handlebars::new()
-            .render_template(
-                template,
-                &serde_json::json!({
-                    "title": self.title,
-                    "theme": theme,
-                    "theme_source": theme_source,
-                    "width": self.width,
-                    "height": self.height,
-                    "chart_id": "chart",
-                    "canvas_type": canvas_type,
-                    "chart_option": chart.to_string(),
-                }),
+        let mut data = self.chart_data("chart", chart)?;
+        data["title"] = self.title.clone().into();
+        data["scripts"] = self.script_source.scripts(&self.theme).into();
+        render_template(include_str!("../asset/charts.html.hbs"), &data)
+    }
+
+    /// Render the chart into an HTML fragment, to be embedded into an existing
+    /// page. The fragment consists of a container `<div>` with the given id
+    /// and a `<script>` initializing the chart, the page has to include the
+    /// scripts returned by [`HtmlRenderer::head`] once.
+    ///
+    /// The id must be unique within the page, otherwise the fragments show
+    /// their charts in the same container. [`HtmlRenderer::unique_id`]
+    /// generates one.
+    pub fn render_fragment(&self, id: &str, chart: &Chart) -> Result<String, EchartsError> {
+        render_template("{{> chart }}", &self.chart_data(id, chart)?)
+    }
+
+    /// An id for [`HtmlRenderer::render_fragment`] that no other call in this
+    /// process returns, e.g. `charming-chart-7`.
+    pub fn unique_id() -> String {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        format!("charming-chart-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// The `<script>` tags loading echarts and the theme, to be placed into
+    /// the `<head>` of a page containing fragments from
+    /// [`HtmlRenderer::render_fragment`].
+    pub fn head(&self) -> Result<String, EchartsError> {
+        render_template(
+            "{{> scripts }}",
+            &serde_json::json!({ "scripts": self.script_source.scripts(&self.theme) }),
+        )
+    }
+
+    fn chart_data(&self, id: &str, chart: &Chart) -> Result<serde_json::Value, EchartsError> {
+        let style = if self.responsive {
+            format!(
+                "width: 100%; aspect-ratio: {} / {}",
+                self.width, self.height
             )
-            .map_err(|error| EchartsError::HtmlRenderingError(error.to_string()))?;
+        } else {
+            format!("width: {}px; height: {}px", self.width, self.height)
+        };
+        let mut data = chart_data(id, chart, &self.theme, style)?;
+        data["responsive"] = self.responsive.into();
         Ok(data)
     }
 
@@ -55,7 +238,6 @@ impl HtmlRenderer {
         path: P,
     ) -> Result<(), EchartsError> {
         let svg = self.render(chart)?;
-        std::fs::write(path, svg)
-            .map_err(|error| EchartsError::ImageRenderingError(error.to_string()))
+        std::fs::write(path, svg).map_err(EchartsError::IoError)
     }
 }
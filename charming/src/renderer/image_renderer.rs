@@ -1,62 +1,154 @@
-use std::pin::pin;
+use std::{
+    cell::Cell,
+    pin::pin,
+    rc::Rc,
+    sync::{
+        Arc, Condvar, Mutex, MutexGuard, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
 
 use deno_core::{JsRuntime, RuntimeOptions, v8};
 use handlebars::Handlebars;
 
 #[cfg(feature = "ssr-raster")]
-use image::RgbaImage;
+use image::{
+    Delay, Frame, RgbaImage,
+    codecs::gif::{GifEncoder, Repeat},
+};
+#[cfg(feature = "ssr-pdf")]
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref};
 #[cfg(feature = "ssr-raster")]
 use resvg::{tiny_skia::Pixmap, usvg};
+#[cfg(feature = "ssr-pdf")]
+use std::collections::HashMap;
 #[cfg(feature = "ssr-raster")]
 use std::io::Cursor;
-#[cfg(feature = "ssr-raster")]
-use std::sync::Arc;
 
-use crate::{Chart, EchartsError, theme::Theme};
+#[cfg(all(feature = "ssr-raster", feature = "terminal"))]
+use crate::TerminalImageProtocol;
+use crate::{Chart, EchartsError, JsException, element::Color, theme::Theme};
 
 static CODE_TEMPLATE: &str = r#"
 {{#if theme_source}}{{{ theme_source }}}{{/if}}
+{{#each register_maps}}
+{{{ this }}}
+{{/each}}
 var chart = echarts.init(null, {{#if theme}}'{{ theme }}'{{else}}null{{/if}}, {
     renderer: 'svg',
     ssr: true,
     width: {{ width }},
-    height: {{ height }}
+    height: {{ height }},
+    devicePixelRatio: {{ pixel_ratio }}
 });
 
-chart.setOption({ animation: false, progressive: 0 });
-chart.setOption({{{ chart_option }}});
-chart.renderToSVGString();
+var svg;
+try {
+    chart.setOption({ animation: false, progressive: 0 });
+    chart.setOption({{{ chart_option }}});
+    {{#if background_color}}
+    chart.setOption({ backgroundColor: {{{ background_color }}} });
+    {{/if}}
+    svg = chart.renderToSVGString();
+} finally {
+    chart.dispose();
+}
+svg;
 "#;
 
+/// A V8 startup snapshot with `runtime.js`, echarts and the built-in themes
+/// already evaluated, created by the build script.
+#[cfg(feature = "ssr-snapshot")]
+static ECHARTS_SNAPSHOT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/ECHARTS_SNAPSHOT.bin"));
+
+/// Tuffy, a small public domain font, loaded when no other font is available.
+#[cfg(feature = "ssr-fallback-font")]
+static FALLBACK_FONT: &[u8] = include_bytes!("../asset/Tuffy.ttf");
+#[cfg(feature = "ssr-fallback-font")]
+static FALLBACK_FONT_FAMILY: &str = "Tuffy";
+
 #[cfg(feature = "ssr-raster")]
 #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
 pub use image::ImageFormat;
 
+/// Options overriding the settings of an [`ImageRenderer`] for a single
+/// render. Settings left unset fall back to the ones of the renderer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RenderOptions {
+    width: Option<u32>,
+    height: Option<u32>,
+    theme: Option<Theme>,
+    background_color: Option<Color>,
+    pixel_ratio: Option<f64>,
+}
+
+impl RenderOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    pub fn size(self, width: u32, height: u32) -> Self {
+        self.width(width).height(height)
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Set the background colour, taking precedence over the one of the chart.
+    pub fn background_color<C: Into<Color>>(mut self, background_color: C) -> Self {
+        self.background_color = Some(background_color.into());
+        self
+    }
+
+    /// Set the device pixel ratio, see [`ImageRenderer::pixel_ratio`].
+    pub fn pixel_ratio(mut self, pixel_ratio: f64) -> Self {
+        self.pixel_ratio = Some(pixel_ratio);
+        self
+    }
+}
+
 pub struct ImageRenderer {
-    js_runtime: JsRuntime,
+    /// The runtime is discarded after an execution was terminated, and created
+    /// again before the next render.
+    js_runtime: Option<JsRuntime>,
+    execution_timeout: Option<Duration>,
+    /// The deadline of the job of an [`ImageRendererPool`](crate::ImageRendererPool)
+    /// being rendered.
+    deadline: Option<Instant>,
+    heap_limit: Option<usize>,
+    heap_limit_reached: Rc<Cell<bool>>,
+    /// Set by a [`RenderCanceller`] before it terminates the execution.
+    cancelled: Arc<AtomicBool>,
+    watchdog: Watchdog,
     #[cfg(feature = "ssr-raster")]
     fontdb: Arc<usvg::fontdb::Database>,
     theme: Theme,
     width: u32,
     height: u32,
+    pixel_ratio: f64,
 }
 
 impl ImageRenderer {
+    /// Create a renderer for charts of the given size.
+    ///
+    /// The JavaScript runtime is created on the first render, which returns
+    /// the error if it cannot be created. See [`ImageRenderer::try_new`] to
+    /// create it right away.
     pub fn new(width: u32, height: u32) -> Self {
-        let mut runtime = JsRuntime::new(RuntimeOptions::default());
-        runtime
-            .execute_script(
-                "[runtime.js]",
-                include_str!("../asset/runtime.js").to_string(),
-            )
-            .unwrap();
-        runtime
-            .execute_script(
-                "[echarts.js]",
-                include_str!("../asset/echarts-5.5.1.min.js").to_string(),
-            )
-            .unwrap();
-
         #[cfg(feature = "ssr-raster")]
         let mut fontdb = usvg::fontdb::Database::default();
         #[cfg(feature = "ssr-raster")]
@@ -71,13 +163,65 @@ impl ImageRenderer {
             set_default_fonts(&mut fontdb);
         }
 
+        #[cfg(feature = "ssr-fallback-font")]
+        set_fallback_font(&mut fontdb);
+
         Self {
-            js_runtime: runtime,
+            js_runtime: None,
+            execution_timeout: None,
+            deadline: None,
+            heap_limit: None,
+            heap_limit_reached: Rc::new(Cell::new(false)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            watchdog: Watchdog::default(),
             #[cfg(feature = "ssr-raster")]
             fontdb: Arc::new(fontdb),
             theme: Theme::Default,
             width,
             height,
+            pixel_ratio: 1.0,
+        }
+    }
+
+    /// Create a renderer for charts of the given size together with its
+    /// JavaScript runtime, returning an error if the runtime cannot be
+    /// created.
+    pub fn try_new(width: u32, height: u32) -> Result<Self, EchartsError> {
+        let mut renderer = Self::new(width, height);
+        renderer.init_runtime()?;
+        Ok(renderer)
+    }
+
+    /// Terminate renders still running at `deadline`, like with
+    /// [`ImageRenderer::execution_timeout`].
+    pub(crate) fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    /// Create the JavaScript runtime if there is none yet, so that the next
+    /// render does not pay for its startup.
+    pub(crate) fn init_runtime(&mut self) -> Result<&mut JsRuntime, EchartsError> {
+        let runtime = match self.js_runtime.take() {
+            Some(runtime) => runtime,
+            None => create_runtime(self.heap_limit, &self.heap_limit_reached)?,
+        };
+        Ok(self.js_runtime.insert(runtime))
+    }
+
+    /// A handle to terminate the renders of this renderer from another thread.
+    pub(crate) fn canceller(&mut self) -> Result<RenderCanceller, EchartsError> {
+        let isolate = self.init_runtime()?.v8_isolate().thread_safe_handle();
+        Ok(RenderCanceller {
+            isolate,
+            cancelled: Arc::clone(&self.cancelled),
+        })
+    }
+
+    /// Forget a cancellation that came too late to stop a render. Its
+    /// termination may still be pending, so the runtime is created again.
+    pub(crate) fn clear_cancelled(&mut self) {
+        if self.cancelled.swap(false, Ordering::SeqCst) {
+            self.js_runtime = None;
         }
     }
 
@@ -86,42 +230,221 @@ impl ImageRenderer {
         self
     }
 
+    /// Set the device pixel ratio, 1 by default. The chart is laid out at its
+    /// logical width and height, while raster images are rendered at
+    /// `pixel_ratio` times that size, e.g. 1600x1200 pixels for an 800x600
+    /// chart with a pixel ratio of 2.
+    pub fn pixel_ratio(mut self, pixel_ratio: f64) -> Self {
+        self.pixel_ratio = pixel_ratio;
+        self
+    }
+
+    /// Set the time the JavaScript of a single render may run. A render taking
+    /// longer is terminated with [`EchartsError::TimeoutError`], e.g. when a
+    /// formatter function loops forever.
+    pub fn execution_timeout(mut self, timeout: Duration) -> Self {
+        self.execution_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum size of the JavaScript heap in bytes. A render
+    /// exceeding it is terminated with [`EchartsError::HeapLimitError`],
+    /// instead of aborting the process.
+    ///
+    /// The limit is applied when the JavaScript runtime is created, on the
+    /// first render of a renderer from [`ImageRenderer::new`]. A runtime
+    /// already created by [`ImageRenderer::try_new`] is created again.
+    pub fn heap_limit(mut self, bytes: usize) -> Self {
+        self.heap_limit = Some(bytes);
+        self.js_runtime = None;
+        self
+    }
+
+    /// Load a font file (`.ttf`, `.otf`, `.ttc` or `.otc`) to render text
+    /// with.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn load_font_file<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), EchartsError> {
+        let path = path.as_ref();
+        Arc::make_mut(&mut self.fontdb)
+            .load_font_file(path)
+            .map_err(|error| EchartsError::FontError(format!("{}: {error}", path.display())))
+    }
+
+    /// Load all font files in a directory and its subdirectories.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn load_fonts_dir<P: AsRef<std::path::Path>>(&mut self, path: P) {
+        Arc::make_mut(&mut self.fontdb).load_fonts_dir(path);
+    }
+
+    /// Load a font from its bytes, e.g. one embedded with `include_bytes!`.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn load_font_data(&mut self, data: Vec<u8>) {
+        Arc::make_mut(&mut self.fontdb).load_font_data(data);
+    }
+
+    /// Set the font family used for the generic `sans-serif` family, which is
+    /// the default font family of echarts.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn set_sans_serif_family<S: Into<String>>(&mut self, family: S) {
+        Arc::make_mut(&mut self.fontdb).set_sans_serif_family(family);
+    }
+
+    /// Set the font family used for the generic `serif` family.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn set_serif_family<S: Into<String>>(&mut self, family: S) {
+        Arc::make_mut(&mut self.fontdb).set_serif_family(family);
+    }
+
+    /// Set the font family used for the generic `monospace` family.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn set_monospace_family<S: Into<String>>(&mut self, family: S) {
+        Arc::make_mut(&mut self.fontdb).set_monospace_family(family);
+    }
+
     /// Render chart to an SVG String
     pub fn render(&mut self, chart: &Chart) -> Result<String, EchartsError> {
-        let (theme, theme_source) = self.theme.to_str();
+        self.render_with(chart, &RenderOptions::default())
+    }
+
+    /// Render chart to an SVG String, overriding the settings of the renderer
+    /// with `options`.
+    pub fn render_with(
+        &mut self,
+        chart: &Chart,
+        options: &RenderOptions,
+    ) -> Result<String, EchartsError> {
+        self.render_svg(chart, options)
+    }
+
+    /// Render many charts to SVG Strings, each one with its own
+    /// [`RenderOptions`]. A failing chart does not abort the batch, its error
+    /// is returned in its place. Charts are rendered lazily as the returned
+    /// iterator is consumed.
+    pub fn render_batch<'a, I>(
+        &'a mut self,
+        items: I,
+    ) -> impl Iterator<Item = Result<String, EchartsError>> + 'a
+    where
+        I: IntoIterator<Item = (Chart, RenderOptions)>,
+        I::IntoIter: 'a,
+    {
+        items
+            .into_iter()
+            .map(move |(chart, options)| self.render_svg(&chart, &options))
+    }
+
+    pub(crate) fn render_svg(
+        &mut self,
+        chart: &Chart,
+        options: &RenderOptions,
+    ) -> Result<String, EchartsError> {
+        let theme = options.theme.as_ref().unwrap_or(&self.theme);
+        let (theme_name, theme_source) = theme.to_str();
+        // The built-in themes are part of the snapshot already.
+        #[cfg(feature = "ssr-snapshot")]
+        let theme_source = match theme {
+            Theme::Custom(..) => theme_source,
+            _ => "",
+        };
+        let (width, height) = self.size(options);
+        let pixel_ratio = self.device_pixel_ratio(options)?;
+        let background_color = options
+            .background_color
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
         let code = Handlebars::new()
             .render_template(
                 CODE_TEMPLATE,
                 &serde_json::json!({
-                    "theme": theme,
+                    "theme": theme_name,
                     "theme_source": theme_source,
-                    "width": self.width,
-                    "height": self.height,
-                    "chart_option": chart.to_string(),
+                    "width": width,
+                    "height": height,
+                    "pixel_ratio": pixel_ratio,
+                    "background_color": background_color,
+                    "register_maps": chart.register_map_scripts(),
+                    "chart_option": chart.to_json()?,
                 }),
             )
-            .expect("Failed to render template");
-        let result = self.js_runtime.execute_script("[anon]", code);
-
-        match result {
-            Ok(global) => {
-                let context = self.js_runtime.main_context();
-                let mut scope = pin!(v8::HandleScope::new(self.js_runtime.v8_isolate()));
-                let mut scope = scope.as_mut().init();
-                let context_local = v8::Local::new(&scope, context);
-                let mut scope = v8::ContextScope::new(&mut scope, context_local);
-                let local = v8::Local::new(&scope, global);
-                let value = serde_v8::from_v8::<serde_json::Value>(&mut scope, local);
-
-                match value {
-                    Ok(value) => Ok(value.as_str().unwrap().to_string()),
-                    Err(error) => Err(EchartsError::JsRuntimeError(error.to_string())),
-                }
-            }
-            Err(error) => Err(EchartsError::JsRuntimeError(error.to_string())),
+            .map_err(|error| EchartsError::TemplateError(Box::new(error)))?;
+
+        let mut runtime = match self.js_runtime.take() {
+            Some(runtime) => runtime,
+            None => create_runtime(self.heap_limit, &self.heap_limit_reached)?,
+        };
+        let time_limit = self.time_limit();
+        if let Some(timeout) = time_limit {
+            self.watchdog
+                .arm(runtime.v8_isolate().thread_safe_handle(), timeout);
+        }
+        let result = runtime.execute_script("[anon]", code);
+        let timed_out = time_limit.is_some() && self.watchdog.disarm();
+
+        // A terminated runtime is dropped here, to be created again on the
+        // next render.
+        if self.heap_limit_reached.replace(false) {
+            return Err(EchartsError::HeapLimitError(format!(
+                "the JavaScript heap exceeded its limit of {} bytes",
+                self.heap_limit.unwrap_or_default()
+            )));
+        }
+        if timed_out {
+            return Err(EchartsError::TimeoutError(time_limit.unwrap_or_default()));
+        }
+        if self.cancelled.swap(false, Ordering::SeqCst) {
+            return Err(EchartsError::Cancelled);
+        }
+
+        let svg = match result {
+            Ok(global) => read_string(&mut runtime, global),
+            Err(error) => Err(js_exception(*error)),
+        };
+        self.js_runtime = Some(runtime);
+        svg
+    }
+
+    /// The time the JavaScript of a render may run, given the execution
+    /// timeout and the deadline.
+    fn time_limit(&self) -> Option<Duration> {
+        let remaining = self
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        match (self.execution_timeout, remaining) {
+            (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+            (timeout, remaining) => timeout.or(remaining),
         }
     }
 
+    /// The size of the chart, given the settings of the renderer and `options`.
+    fn size(&self, options: &RenderOptions) -> (u32, u32) {
+        (
+            options.width.unwrap_or(self.width),
+            options.height.unwrap_or(self.height),
+        )
+    }
+
+    /// The pixel ratio of the chart, given the settings of the renderer and
+    /// `options`, which must be a positive number.
+    fn device_pixel_ratio(&self, options: &RenderOptions) -> Result<f64, EchartsError> {
+        let pixel_ratio = options.pixel_ratio.unwrap_or(self.pixel_ratio);
+        if !pixel_ratio.is_finite() || pixel_ratio <= 0.0 {
+            return Err(EchartsError::InvalidSizeError(format!(
+                "pixel ratio {pixel_ratio} is not a positive number"
+            )));
+        }
+        Ok(pixel_ratio)
+    }
+
     /// Render a chart to a given image format in bytes
     #[cfg(feature = "ssr-raster")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
@@ -130,42 +453,98 @@ impl ImageRenderer {
         image_format: ImageFormat,
         chart: &Chart,
     ) -> Result<Vec<u8>, EchartsError> {
-        let svg = self.render(chart)?;
+        self.render_format_with(image_format, chart, &RenderOptions::default())
+    }
 
-        let img = self.render_svg_to_buf(&svg)?;
+    /// Render a chart to a given image format in bytes, overriding the
+    /// settings of the renderer with `options`.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn render_format_with(
+        &mut self,
+        image_format: ImageFormat,
+        chart: &Chart,
+        options: &RenderOptions,
+    ) -> Result<Vec<u8>, EchartsError> {
+        let img = self.render_image(chart, options)?;
 
         // give buf initial capacity of: width * height * num of channels for RGBA + room for headers/metadata
-        let estimated_capacity = self.width * self.height * 4 + 1024;
+        let estimated_capacity = img.width() * img.height() * 4 + 1024;
         let mut buf = Vec::with_capacity(estimated_capacity as usize);
         img.write_to(&mut Cursor::new(&mut buf), image_format)
-            .map_err(|error| EchartsError::ImageRenderingError(error.to_string()))?;
+            .map_err(image_error)?;
         Ok(buf)
     }
 
+    /// Render a chart as the escape sequence of a terminal graphics protocol,
+    /// which displays the chart inline when printed to a terminal supporting
+    /// the protocol.
+    #[cfg(all(feature = "ssr-raster", feature = "terminal"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "ssr-raster", feature = "terminal"))))]
+    pub fn render_terminal(
+        &mut self,
+        protocol: TerminalImageProtocol,
+        chart: &Chart,
+    ) -> Result<String, EchartsError> {
+        let img = self.render_image(chart, &RenderOptions::default())?;
+        Ok(protocol.encode(&img))
+    }
+
+    /// Render a chart into an [`image::ImageBuffer`]
+    #[cfg(feature = "ssr-raster")]
+    fn render_image(
+        &mut self,
+        chart: &Chart,
+        options: &RenderOptions,
+    ) -> Result<image::RgbaImage, EchartsError> {
+        let svg = self.render_svg(chart, options)?;
+        let (width, height) = self.size(options);
+        let pixel_ratio = self.device_pixel_ratio(options)?;
+        self.render_svg_to_buf(&svg, width, height, pixel_ratio)
+    }
+
     /// Given an svg str, render it into an [`image::ImageBuffer`]
     #[cfg(feature = "ssr-raster")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
-    fn render_svg_to_buf(&mut self, svg: &str) -> Result<image::RgbaImage, EchartsError> {
-        let mut pixels =
-            Pixmap::new(self.width, self.height).ok_or(EchartsError::ImageRenderingError(
-                "Rendered image cannot be greater than i32::MAX/4".to_string(),
-            ))?;
+    fn render_svg_to_buf(
+        &mut self,
+        svg: &str,
+        width: u32,
+        height: u32,
+        pixel_ratio: f64,
+    ) -> Result<image::RgbaImage, EchartsError> {
+        let width = (f64::from(width) * pixel_ratio).round() as u32;
+        let height = (f64::from(height) * pixel_ratio).round() as u32;
+        let mut pixels = Pixmap::new(width, height).ok_or(EchartsError::InvalidSizeError(
+            format!("{width}x{height} pixels cannot be zero or greater than i32::MAX/4"),
+        ))?;
+
+        let tree = self.parse_svg(svg)?;
+        let scale = pixel_ratio as f32;
+        resvg::render(
+            &tree,
+            usvg::Transform::from_scale(scale, scale),
+            &mut pixels.as_mut(),
+        );
 
+        let img = RgbaImage::from_vec(width, height, pixels.take()).ok_or(
+            EchartsError::InvalidSizeError(format!(
+                "{width}x{height} pixels do not fit in an image buffer"
+            )),
+        )?;
+
+        Ok(img)
+    }
+
+    /// Parse an svg str into a [`usvg::Tree`], using the fonts of the renderer.
+    #[cfg(feature = "ssr-raster")]
+    fn parse_svg(&self, svg: &str) -> Result<usvg::Tree, EchartsError> {
         let options = usvg::Options {
             fontdb: Arc::clone(&self.fontdb),
             ..Default::default()
         };
-        let tree = usvg::Tree::from_data(svg.as_bytes(), &options)
-            .map_err(|error| EchartsError::ImageRenderingError(error.to_string()))?;
-        resvg::render(&tree, usvg::Transform::identity(), &mut pixels.as_mut());
-
-        let img = RgbaImage::from_vec(self.width, self.height, pixels.take()).ok_or(
-            EchartsError::ImageRenderingError(
-                "Could not create ImageBuffer from bytes".to_string(),
-            ),
-        )?;
-
-        Ok(img)
+        usvg::Tree::from_data(svg.as_bytes(), &options)
+            .map_err(|error| EchartsError::SvgParseError(Box::new(error)))
     }
 
     /// Render and save chart as an SVG
@@ -174,9 +553,19 @@ impl ImageRenderer {
         chart: &Chart,
         path: P,
     ) -> Result<(), EchartsError> {
-        let svg = self.render(chart)?;
-        std::fs::write(path, svg)
-            .map_err(|error| EchartsError::ImageRenderingError(error.to_string()))
+        self.save_with(chart, path, &RenderOptions::default())
+    }
+
+    /// Render and save chart as an SVG, overriding the settings of the
+    /// renderer with `options`.
+    pub fn save_with<P: AsRef<std::path::Path>>(
+        &mut self,
+        chart: &Chart,
+        path: P,
+        options: &RenderOptions,
+    ) -> Result<(), EchartsError> {
+        let svg = self.render_svg(chart, options)?;
+        std::fs::write(path, svg).map_err(EchartsError::IoError)
     }
 
     /// Render and save chart as the given image format
@@ -188,10 +577,242 @@ impl ImageRenderer {
         chart: &Chart,
         path: P,
     ) -> Result<(), EchartsError> {
-        let svg = self.render(chart)?;
-        let img = self.render_svg_to_buf(&svg)?;
+        self.save_format_with(image_format, chart, path, &RenderOptions::default())
+    }
+
+    /// Render and save chart as the given image format, overriding the
+    /// settings of the renderer with `options`.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn save_format_with<P: AsRef<std::path::Path>>(
+        &mut self,
+        image_format: ImageFormat,
+        chart: &Chart,
+        path: P,
+        options: &RenderOptions,
+    ) -> Result<(), EchartsError> {
+        let img = self.render_image(chart, options)?;
         img.save_with_format(path, image_format)
-            .map_err(|error| EchartsError::ImageRenderingError(error.to_string()))
+            .map_err(image_error)
+    }
+
+    /// Render charts as the frames of an animated GIF in bytes, e.g. the
+    /// states of a chart as its data is updated. Each frame is shown for
+    /// `frame_delay`, and the animation loops forever.
+    ///
+    /// GIF has no partial transparency, so transparent parts of the charts,
+    /// e.g. of a chart without a background colour, are filled with white
+    /// rather than leaving jagged edges.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn render_gif<'a, I>(
+        &mut self,
+        frames: I,
+        frame_delay: Duration,
+    ) -> Result<Vec<u8>, EchartsError>
+    where
+        I: IntoIterator<Item = &'a Chart>,
+    {
+        self.render_gif_with(frames, frame_delay, &RenderOptions::default())
+    }
+
+    /// Render charts as the frames of an animated GIF in bytes, overriding
+    /// the settings of the renderer with `options`, see
+    /// [`ImageRenderer::render_gif`].
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn render_gif_with<'a, I>(
+        &mut self,
+        frames: I,
+        frame_delay: Duration,
+        options: &RenderOptions,
+    ) -> Result<Vec<u8>, EchartsError>
+    where
+        I: IntoIterator<Item = &'a Chart>,
+    {
+        let delay = Delay::from_saturating_duration(frame_delay);
+        let mut buf = Vec::new();
+        {
+            let mut encoder = GifEncoder::new_with_speed(&mut buf, 10);
+            encoder.set_repeat(Repeat::Infinite).map_err(image_error)?;
+            for chart in frames {
+                let mut img = self.render_image(chart, options)?;
+                fill_transparency(&mut img);
+                encoder
+                    .encode_frame(Frame::from_parts(img, 0, 0, delay))
+                    .map_err(image_error)?;
+            }
+        }
+        Ok(buf)
+    }
+
+    /// Render and save charts as an animated GIF, see
+    /// [`ImageRenderer::render_gif`].
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn save_gif<'a, I, P>(
+        &mut self,
+        frames: I,
+        frame_delay: Duration,
+        path: P,
+    ) -> Result<(), EchartsError>
+    where
+        I: IntoIterator<Item = &'a Chart>,
+        P: AsRef<std::path::Path>,
+    {
+        self.save_gif_with(frames, frame_delay, path, &RenderOptions::default())
+    }
+
+    /// Render and save charts as an animated GIF, overriding the settings of
+    /// the renderer with `options`, see [`ImageRenderer::render_gif`].
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn save_gif_with<'a, I, P>(
+        &mut self,
+        frames: I,
+        frame_delay: Duration,
+        path: P,
+        options: &RenderOptions,
+    ) -> Result<(), EchartsError>
+    where
+        I: IntoIterator<Item = &'a Chart>,
+        P: AsRef<std::path::Path>,
+    {
+        let gif = self.render_gif_with(frames, frame_delay, options)?;
+        std::fs::write(path, gif).map_err(EchartsError::IoError)
+    }
+
+    /// Render and save charts as a numbered sequence of PNG images in `dir`,
+    /// named `frame-0000.png`, `frame-0001.png` and so on, e.g. to be encoded
+    /// into a video. Returns the paths of the images.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn save_frames<'a, I, P>(
+        &mut self,
+        frames: I,
+        dir: P,
+    ) -> Result<Vec<std::path::PathBuf>, EchartsError>
+    where
+        I: IntoIterator<Item = &'a Chart>,
+        P: AsRef<std::path::Path>,
+    {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir).map_err(EchartsError::IoError)?;
+
+        let mut paths = vec![];
+        for (index, chart) in frames.into_iter().enumerate() {
+            let path = dir.join(format!("frame-{index:04}.png"));
+            self.save_format(ImageFormat::Png, chart, &path)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Render a chart to a PDF document in bytes. The chart is converted to
+    /// vector graphics with its fonts embedded, on a page of the size of the
+    /// chart, taking a pixel as a point.
+    #[cfg(feature = "ssr-pdf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-pdf")))]
+    pub fn render_pdf(&mut self, chart: &Chart) -> Result<Vec<u8>, EchartsError> {
+        self.render_pdf_with(chart, &RenderOptions::default())
+    }
+
+    /// Render a chart to a PDF document in bytes, overriding the settings of
+    /// the renderer with `options`.
+    #[cfg(feature = "ssr-pdf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-pdf")))]
+    pub fn render_pdf_with(
+        &mut self,
+        chart: &Chart,
+        options: &RenderOptions,
+    ) -> Result<Vec<u8>, EchartsError> {
+        self.render_pdf_pages([(chart.clone(), options.clone())])
+    }
+
+    /// Render many charts to a single PDF document in bytes, with one page
+    /// per chart. Returns an [`EchartsError::InvalidSizeError`] when there
+    /// are no charts, as a document needs at least one page.
+    #[cfg(feature = "ssr-pdf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-pdf")))]
+    pub fn render_pdf_pages<I>(&mut self, pages: I) -> Result<Vec<u8>, EchartsError>
+    where
+        I: IntoIterator<Item = (Chart, RenderOptions)>,
+    {
+        let mut alloc = Ref::new(1);
+        let catalog_id = alloc.bump();
+        let page_tree_id = alloc.bump();
+        let mut pdf = Pdf::new();
+        let mut page_ids = vec![];
+
+        for (chart, options) in pages {
+            let svg = self.render_svg(&chart, &options)?;
+            let tree = self.parse_svg(&svg)?;
+            let (chunk, svg_id) = svg2pdf::to_chunk(&tree, svg2pdf::ConversionOptions::default())
+                // `ConversionError` does not implement `Error`.
+                .map_err(|error| EchartsError::PdfError(error.to_string().into()))?;
+
+            // Move the objects of the chart after the ones already written.
+            let mut ids = HashMap::new();
+            let chunk = chunk.renumber(|old| *ids.entry(old).or_insert_with(|| alloc.bump()));
+            pdf.extend(&chunk);
+
+            let page_id = alloc.bump();
+            let content_id = alloc.bump();
+            let svg_name = Name(b"chart");
+            let (width, height) = (tree.size().width(), tree.size().height());
+
+            let mut page = pdf.page(page_id);
+            page.media_box(Rect::new(0.0, 0.0, width, height));
+            page.parent(page_tree_id);
+            page.contents(content_id);
+            page.resources().x_objects().pair(svg_name, ids[&svg_id]);
+            page.finish();
+
+            // The chart is a form of unit size, scale it to the page.
+            let mut content = Content::new();
+            content.transform([width, 0.0, 0.0, height, 0.0, 0.0]);
+            content.x_object(svg_name);
+            pdf.stream(content_id, &content.finish());
+
+            page_ids.push(page_id);
+        }
+        if page_ids.is_empty() {
+            return Err(EchartsError::InvalidSizeError(
+                "a PDF document needs at least one page".to_string(),
+            ));
+        }
+
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id)
+            .count(page_ids.len() as i32)
+            .kids(page_ids);
+
+        Ok(pdf.finish())
+    }
+
+    /// Render and save chart as a PDF document
+    #[cfg(feature = "ssr-pdf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-pdf")))]
+    pub fn save_pdf<P: AsRef<std::path::Path>>(
+        &mut self,
+        chart: &Chart,
+        path: P,
+    ) -> Result<(), EchartsError> {
+        let pdf = self.render_pdf(chart)?;
+        std::fs::write(path, pdf).map_err(EchartsError::IoError)
+    }
+
+    /// Render and save many charts as a single PDF document, with one page
+    /// per chart.
+    #[cfg(feature = "ssr-pdf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-pdf")))]
+    pub fn save_pdf_pages<I, P>(&mut self, pages: I, path: P) -> Result<(), EchartsError>
+    where
+        I: IntoIterator<Item = (Chart, RenderOptions)>,
+        P: AsRef<std::path::Path>,
+    {
+        let pdf = self.render_pdf_pages(pages)?;
+        std::fs::write(path, pdf).map_err(EchartsError::IoError)
     }
 }
 
@@ -248,10 +869,240 @@ fn set_default_fonts(fontdb: &mut usvg::fontdb::Database) {
     }
 }
 
-#[cfg(all(
-    feature = "ssr-raster",
-    unix,
-    not(any(target_os = "macos", target_os = "android"))
+/// Create a runtime with echarts loaded. With a heap limit, reaching the limit
+/// terminates the execution and sets `heap_limit_reached`.
+fn create_runtime(
+    heap_limit: Option<usize>,
+    heap_limit_reached: &Rc<Cell<bool>>,
+) -> Result<JsRuntime, EchartsError> {
+    let create_params = heap_limit.map(|limit| v8::CreateParams::default().heap_limits(0, limit));
+
+    #[cfg(feature = "ssr-snapshot")]
+    let mut runtime = JsRuntime::try_new(RuntimeOptions {
+        startup_snapshot: Some(ECHARTS_SNAPSHOT),
+        create_params,
+        ..Default::default()
+    })
+    .map_err(|error| EchartsError::RuntimeCreationError(Box::new(error)))?;
+
+    #[cfg(not(feature = "ssr-snapshot"))]
+    let mut runtime = JsRuntime::try_new(RuntimeOptions {
+        create_params,
+        ..Default::default()
+    })
+    .map_err(|error| EchartsError::RuntimeCreationError(Box::new(error)))?;
+
+    if heap_limit.is_some() {
+        let handle = runtime.v8_isolate().thread_safe_handle();
+        let heap_limit_reached = Rc::clone(heap_limit_reached);
+        runtime.add_near_heap_limit_callback(move |current_limit, _| {
+            heap_limit_reached.set(true);
+            handle.terminate_execution();
+            // Leave room for the execution to unwind, the runtime is dropped
+            // afterwards.
+            current_limit * 2
+        });
+    }
+
+    #[cfg(not(feature = "ssr-snapshot"))]
+    {
+        runtime
+            .execute_script(
+                "[runtime.js]",
+                include_str!("../asset/runtime.js").to_string(),
+            )
+            .map_err(|error| js_exception(*error))?;
+        runtime
+            .execute_script(
+                "[echarts.js]",
+                include_str!("../asset/echarts-5.5.1.min.js").to_string(),
+            )
+            .map_err(|error| js_exception(*error))?;
+    }
+
+    Ok(runtime)
+}
+
+/// Read the value a script evaluated to as a String.
+fn read_string(
+    runtime: &mut JsRuntime,
+    global: v8::Global<v8::Value>,
+) -> Result<String, EchartsError> {
+    let context = runtime.main_context();
+    let mut scope = pin!(v8::HandleScope::new(runtime.v8_isolate()));
+    let mut scope = scope.as_mut().init();
+    let context_local = v8::Local::new(&scope, context);
+    let mut scope = v8::ContextScope::new(&mut scope, context_local);
+    let local = v8::Local::new(&scope, global);
+    serde_v8::from_v8::<String>(&mut scope, local)
+        .map_err(|error| EchartsError::ResultConversionError(Box::new(error)))
+}
+
+/// Terminates the renders of an [`ImageRenderer`] from another thread, see
+/// [`ImageRenderer::canceller`].
+pub(crate) struct RenderCanceller {
+    isolate: v8::IsolateHandle,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl RenderCanceller {
+    pub(crate) fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.isolate.terminate_execution();
+    }
+}
+
+/// Terminates the execution of an isolate unless disarmed within a timeout.
+///
+/// The watchdog of a renderer runs on one thread, started on the first
+/// render with a timeout and re-armed for every render after it.
+#[derive(Default)]
+struct Watchdog {
+    shared: Arc<WatchdogShared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[derive(Default)]
+struct WatchdogShared {
+    state: Mutex<WatchdogState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct WatchdogState {
+    /// The isolate to terminate at the deadline.
+    armed: Option<(v8::IsolateHandle, Instant)>,
+    /// Whether the execution was terminated since the watchdog was armed.
+    fired: bool,
+    stopped: bool,
+}
+
+impl Watchdog {
+    fn arm(&mut self, isolate: v8::IsolateHandle, timeout: Duration) {
+        if self.thread.is_none() {
+            let shared = Arc::clone(&self.shared);
+            self.thread = std::thread::Builder::new()
+                .name("charming-watchdog".to_string())
+                .spawn(move || shared.watch())
+                .ok();
+        }
+
+        let mut state = self.shared.lock();
+        state.armed = Some((isolate, Instant::now() + timeout));
+        state.fired = false;
+        self.shared.changed.notify_one();
+    }
+
+    /// Disarm the watchdog, returning whether it terminated the execution.
+    /// Once disarmed, it does not terminate the execution anymore.
+    fn disarm(&mut self) -> bool {
+        let mut state = self.shared.lock();
+        state.armed = None;
+        std::mem::take(&mut state.fired)
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.shared.lock().stopped = true;
+        self.shared.changed.notify_one();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl WatchdogShared {
+    fn watch(&self) {
+        let mut state = self.lock();
+        while !state.stopped {
+            let now = Instant::now();
+            state = match state.armed.as_ref().map(|(_, deadline)| *deadline) {
+                Some(deadline) if now >= deadline => {
+                    if let Some((isolate, _)) = state.armed.take() {
+                        state.fired = isolate.terminate_execution();
+                    }
+                    state
+                }
+                Some(deadline) => {
+                    self.changed
+                        .wait_timeout(state, deadline - now)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .changed
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, WatchdogState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn js_exception(error: deno_core::error::JsError) -> EchartsError {
+    let frame = error
+        .source_line_frame_index
+        .and_then(|index| error.frames.get(index))
+        .or(error.frames.first());
+    EchartsError::JsException(Box::new(JsException {
+        message: error.exception_message,
+        stack: error.stack,
+        source_line: error.source_line,
+        line_number: frame.and_then(|frame| frame.line_number),
+        column_number: frame.and_then(|frame| frame.column_number),
+    }))
+}
+
+/// Blend the transparent parts of an image onto white.
+#[cfg(feature = "ssr-raster")]
+fn fill_transparency(img: &mut RgbaImage) {
+    for pixel in img.pixels_mut() {
+        let alpha = u16::from(pixel[3]);
+        for channel in &mut pixel.0[..3] {
+            *channel = ((u16::from(*channel) * alpha + 255 * (255 - alpha)) / 255) as u8;
+        }
+        pixel[3] = 255;
+    }
+}
+
+#[cfg(feature = "ssr-raster")]
+fn image_error(error: image::ImageError) -> EchartsError {
+    match error {
+        image::ImageError::IoError(error) => EchartsError::IoError(error),
+        error => EchartsError::ImageEncodingError(Box::new(error)),
+    }
+}
+
+/// Load the fallback font and use it for the generic families that have no
+/// font installed.
+#[cfg(feature = "ssr-fallback-font")]
+fn set_fallback_font(fontdb: &mut usvg::fontdb::Database) {
+    use usvg::fontdb::Family;
+
+    fontdb.load_font_data(FALLBACK_FONT.to_vec());
+
+    if !font_exists(fontdb, fontdb.family_name(&Family::SansSerif)) {
+        fontdb.set_sans_serif_family(FALLBACK_FONT_FAMILY);
+    }
+    if !font_exists(fontdb, fontdb.family_name(&Family::Serif)) {
+        fontdb.set_serif_family(FALLBACK_FONT_FAMILY);
+    }
+    if !font_exists(fontdb, fontdb.family_name(&Family::Monospace)) {
+        fontdb.set_monospace_family(FALLBACK_FONT_FAMILY);
+    }
+}
+
+#[cfg(any(
+    feature = "ssr-fallback-font",
+    all(
+        feature = "ssr-raster",
+        unix,
+        not(any(target_os = "macos", target_os = "android"))
+    )
 ))]
 #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
 fn font_exists(fontdb: &usvg::fontdb::Database, family: &str) -> bool {
@@ -264,3 +1115,68 @@ fn font_exists(fontdb: &usvg::fontdb::Database, family: &str) -> bool {
         })
         .is_some()
 }
+
+#[cfg(all(test, feature = "ssr-raster"))]
+mod test {
+    use usvg::fontdb::Family;
+
+    use super::*;
+
+    static TUFFY: &[u8] = include_bytes!("../asset/Tuffy.ttf");
+
+    #[test]
+    fn load_font_data_adds_the_font() {
+        let mut renderer = ImageRenderer::new(400, 300);
+        let faces = renderer.fontdb.len();
+
+        renderer.load_font_data(TUFFY.to_vec());
+        assert_eq!(renderer.fontdb.len(), faces + 1);
+        assert!(
+            renderer
+                .fontdb
+                .faces()
+                .any(|face| face.families.iter().any(|(family, _)| family == "Tuffy"))
+        );
+    }
+
+    #[test]
+    fn set_family_changes_the_generic_families() {
+        let mut renderer = ImageRenderer::new(400, 300);
+        renderer.load_font_data(TUFFY.to_vec());
+        renderer.set_sans_serif_family("Tuffy");
+        renderer.set_serif_family("Tuffy");
+        renderer.set_monospace_family("Tuffy");
+
+        assert_eq!(renderer.fontdb.family_name(&Family::SansSerif), "Tuffy");
+        assert_eq!(renderer.fontdb.family_name(&Family::Serif), "Tuffy");
+        assert_eq!(renderer.fontdb.family_name(&Family::Monospace), "Tuffy");
+    }
+
+    #[cfg(feature = "ssr-fallback-font")]
+    #[test]
+    fn fallback_font_replaces_missing_families() {
+        let mut fontdb = usvg::fontdb::Database::new();
+        fontdb.set_sans_serif_family("No Such Font");
+        fontdb.set_serif_family("No Such Font");
+        fontdb.set_monospace_family("No Such Font");
+
+        set_fallback_font(&mut fontdb);
+        assert!(font_exists(&fontdb, FALLBACK_FONT_FAMILY));
+        assert_eq!(fontdb.family_name(&Family::SansSerif), FALLBACK_FONT_FAMILY);
+        assert_eq!(fontdb.family_name(&Family::Serif), FALLBACK_FONT_FAMILY);
+        assert_eq!(fontdb.family_name(&Family::Monospace), FALLBACK_FONT_FAMILY);
+    }
+
+    #[cfg(feature = "ssr-fallback-font")]
+    #[test]
+    fn fallback_font_keeps_existing_families() {
+        let mut fontdb = usvg::fontdb::Database::new();
+        fontdb.load_font_data(TUFFY.to_vec());
+        fontdb.set_sans_serif_family("Tuffy");
+        fontdb.set_serif_family("No Such Font");
+
+        set_fallback_font(&mut fontdb);
+        assert_eq!(fontdb.family_name(&Family::SansSerif), "Tuffy");
+        assert_eq!(fontdb.family_name(&Family::Serif), FALLBACK_FONT_FAMILY);
+    }
+}
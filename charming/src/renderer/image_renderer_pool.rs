@@ -0,0 +1,471 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex, MutexGuard, Once, PoisonError, Weak, mpsc},
+    task::{Context, Poll, Wake, Waker},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "ssr-raster")]
+use super::image_renderer::ImageFormat;
+use super::image_renderer::{ImageRenderer, RenderCanceller, RenderOptions};
+use crate::{Chart, EchartsError};
+
+type Job = Box<dyn FnOnce(&mut ImageRenderer) + Send>;
+type Factory = Arc<dyn Fn() -> ImageRenderer + Send + Sync>;
+
+/// A pool of pre-warmed [`ImageRenderer`]s, each living on its own thread.
+///
+/// [`ImageRenderer`] is expensive to create and cannot be moved between
+/// threads. The pool creates its renderers once and hands out render jobs to
+/// them, so it can be shared between threads (e.g. in the state of a web
+/// server) and used from both synchronous and asynchronous code.
+///
+/// ```no_run
+/// use charming::{Chart, ImageRenderer, ImageRendererPool};
+/// use charming::theme::Theme;
+///
+/// let pool = ImageRendererPool::new(4, || ImageRenderer::new(800, 600).theme(Theme::Dark));
+/// let svg = pool.render(&Chart::new()).unwrap();
+/// ```
+pub struct ImageRendererPool {
+    sender: Option<mpsc::Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+    timeout: Option<Duration>,
+}
+
+impl ImageRendererPool {
+    /// Create a pool of `size` renderers, each one created by `factory` on its
+    /// own thread.
+    pub fn new<F>(size: usize, factory: F) -> Self
+    where
+        F: Fn() -> ImageRenderer + Send + Sync + 'static,
+    {
+        let factory: Factory = Arc::new(factory);
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..size.max(1))
+            .filter_map(|index| {
+                let factory = Arc::clone(&factory);
+                let receiver = Arc::clone(&receiver);
+                std::thread::Builder::new()
+                    .name(format!("charming-renderer-{index}"))
+                    .spawn(move || work(factory, receiver))
+                    .ok()
+            })
+            .collect();
+
+        Self {
+            sender: Some(sender),
+            workers,
+            timeout: None,
+        }
+    }
+
+    /// Set the time a render job may take, including the time it waits for a
+    /// free renderer. A job that has not started when its timeout expires is
+    /// dropped, and a running one is terminated like with
+    /// [`ImageRenderer::execution_timeout`], which frees its renderer.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The number of renderers in the pool, not counting ones whose thread
+    /// stopped because `factory` panicked.
+    pub fn size(&self) -> usize {
+        self.workers
+            .iter()
+            .filter(|worker| !worker.is_finished())
+            .count()
+    }
+
+    /// Render chart to an SVG String, blocking until the result is available.
+    pub fn render(&self, chart: &Chart) -> Result<String, EchartsError> {
+        self.render_async(chart).wait()
+    }
+
+    /// Render chart to an SVG String, without blocking the current thread.
+    pub fn render_async(&self, chart: &Chart) -> RenderJob<String> {
+        let chart = chart.clone();
+        self.submit(move |renderer| renderer.render(&chart))
+    }
+
+    /// Render many charts to SVG Strings in parallel across the renderers of
+    /// the pool, blocking until all of them are done. A failing chart does not
+    /// abort the batch, its error is returned in its place.
+    ///
+    /// Jobs are handed to the pool a few at a time, so the timeout of the pool
+    /// applies to each chart rather than to the whole batch.
+    pub fn render_batch<I>(&self, items: I) -> Vec<Result<String, EchartsError>>
+    where
+        I: IntoIterator<Item = (Chart, RenderOptions)>,
+    {
+        let max_pending = self.size().max(1) * 2;
+        let mut pending = VecDeque::with_capacity(max_pending);
+        let mut results = Vec::new();
+
+        for (chart, options) in items {
+            if pending.len() >= max_pending
+                && let Some(job) = pending.pop_front()
+            {
+                results.push(RenderJob::wait(job));
+            }
+            pending.push_back(self.submit(move |renderer| renderer.render_svg(&chart, &options)));
+        }
+        results.extend(pending.into_iter().map(RenderJob::wait));
+
+        results
+    }
+
+    /// Render a chart to a given image format in bytes, blocking until the
+    /// result is available.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn render_format(
+        &self,
+        image_format: ImageFormat,
+        chart: &Chart,
+    ) -> Result<Vec<u8>, EchartsError> {
+        self.render_format_async(image_format, chart).wait()
+    }
+
+    /// Render a chart to a given image format in bytes, without blocking the
+    /// current thread.
+    #[cfg(feature = "ssr-raster")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+    pub fn render_format_async(
+        &self,
+        image_format: ImageFormat,
+        chart: &Chart,
+    ) -> RenderJob<Vec<u8>> {
+        let chart = chart.clone();
+        self.submit(move |renderer| renderer.render_format(image_format, &chart))
+    }
+
+    /// Run `render` with the next free renderer of the pool.
+    pub(crate) fn submit<T, F>(&self, render: F) -> RenderJob<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut ImageRenderer) -> Result<T, EchartsError> + Send + 'static,
+    {
+        let timeout = self.timeout;
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let slot = Arc::new(Slot::new());
+        let reply = Reply(Arc::clone(&slot));
+        let job: Job = Box::new(move |renderer| {
+            if reply.0.lock().cancelled {
+                return;
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                reply.send(Err(EchartsError::TimeoutError(timeout.unwrap_or_default())));
+                return;
+            }
+            // Dropping the job from now on terminates its render.
+            let canceller = renderer.canceller().ok();
+            {
+                let mut state = reply.0.lock();
+                if state.cancelled {
+                    return;
+                }
+                state.canceller = canceller;
+            }
+
+            renderer.set_deadline(deadline);
+            let result = match render(renderer) {
+                // The renderer only knows the time that was left of the
+                // timeout of the pool.
+                Err(EchartsError::TimeoutError(_))
+                    if deadline.is_some_and(|deadline| Instant::now() >= deadline) =>
+                {
+                    Err(EchartsError::TimeoutError(timeout.unwrap_or_default()))
+                }
+                result => result,
+            };
+            renderer.set_deadline(None);
+            reply.0.lock().canceller = None;
+            renderer.clear_cancelled();
+            reply.send(result);
+        });
+
+        if let Some(sender) = &self.sender {
+            // A failed send drops the job, which closes its slot.
+            let _ = sender.send(job);
+        }
+
+        RenderJob {
+            slot,
+            timeout,
+            deadline,
+            timer_armed: false,
+        }
+    }
+}
+
+impl Drop for ImageRendererPool {
+    fn drop(&mut self) {
+        // Closing the channel stops the workers once the queue is drained.
+        self.sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn work(factory: Factory, receiver: Arc<Mutex<mpsc::Receiver<Job>>>) {
+    let mut renderer = factory();
+    // An error is returned again by the first render.
+    let _ = renderer.init_runtime();
+    loop {
+        let job = receiver
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .recv();
+        let Ok(job) = job else { break };
+        // A panicking job closes its slot and may leave the renderer in a
+        // broken state, so the worker goes on with a new one.
+        if panic::catch_unwind(AssertUnwindSafe(|| job(&mut renderer))).is_err() {
+            renderer = factory();
+            let _ = renderer.init_runtime();
+        }
+    }
+}
+
+/// A render job submitted to an [`ImageRendererPool`].
+///
+/// The result can be awaited, as `RenderJob` implements [`Future`], or waited
+/// for with [`RenderJob::wait`]. Dropping the job cancels it, and terminates
+/// its render if a renderer is already working on it.
+pub struct RenderJob<T> {
+    slot: Arc<Slot<T>>,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    /// Whether the [`DeadlineTimer`] wakes the job when its deadline passes.
+    timer_armed: bool,
+}
+
+impl<T> RenderJob<T> {
+    /// Block the current thread until the result is available or the timeout
+    /// of the pool expires.
+    pub fn wait(self) -> Result<T, EchartsError> {
+        let mut state = self.slot.lock();
+        loop {
+            if let Some(result) = state.result.take() {
+                return result;
+            }
+            if state.closed {
+                return Err(EchartsError::RendererStopped);
+            }
+            state = match self.deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(EchartsError::TimeoutError(self.timeout.unwrap_or_default()));
+                    }
+                    self.slot
+                        .ready
+                        .wait_timeout(state, deadline - now)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .slot
+                    .ready
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
+
+impl<T: Send + 'static> Future for RenderJob<T> {
+    type Output = Result<T, EchartsError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let job = self.get_mut();
+        {
+            let mut state = job.slot.lock();
+            if let Some(result) = state.result.take() {
+                return Poll::Ready(result);
+            } else if state.closed {
+                return Poll::Ready(Err(EchartsError::RendererStopped));
+            } else if job
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                return Poll::Ready(Err(EchartsError::TimeoutError(
+                    job.timeout.unwrap_or_default(),
+                )));
+            }
+            state.waker = Some(cx.waker().clone());
+        }
+
+        // The job may still be queued, or busy outside of the JavaScript the
+        // watchdog terminates, so it is woken to time out on its own.
+        if let Some(deadline) = job.deadline
+            && !job.timer_armed
+        {
+            let waker = Arc::new(SlotWaker(Arc::downgrade(&job.slot)));
+            DEADLINE_TIMER.wake_at(deadline, waker.into());
+            job.timer_armed = true;
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for RenderJob<T> {
+    fn drop(&mut self) {
+        let mut state = self.slot.lock();
+        state.cancelled = true;
+        if let Some(canceller) = state.canceller.take() {
+            canceller.cancel();
+        }
+    }
+}
+
+/// The place a render result is handed over from a worker to a [`RenderJob`].
+struct Slot<T> {
+    state: Mutex<SlotState<T>>,
+    ready: Condvar,
+}
+
+struct SlotState<T> {
+    result: Option<Result<T, EchartsError>>,
+    closed: bool,
+    cancelled: bool,
+    /// Set while a renderer is working on the job.
+    canceller: Option<RenderCanceller>,
+    waker: Option<Waker>,
+}
+
+impl<T> Slot<T> {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(SlotState {
+                result: None,
+                closed: false,
+                cancelled: false,
+                canceller: None,
+                waker: None,
+            }),
+            ready: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SlotState<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The sending half of a [`Slot`]. Dropping it without sending a result, e.g.
+/// because the worker thread panicked, closes the slot.
+struct Reply<T>(Arc<Slot<T>>);
+
+impl<T> Reply<T> {
+    fn send(&self, result: Result<T, EchartsError>) {
+        let mut state = self.0.lock();
+        state.result = Some(result);
+        self.close(state);
+    }
+
+    fn close(&self, mut state: MutexGuard<'_, SlotState<T>>) {
+        state.closed = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        self.0.ready.notify_all();
+    }
+}
+
+/// Wakes the task awaiting a [`Slot`], whichever task that is by then.
+struct SlotWaker<T>(Weak<Slot<T>>);
+
+impl<T: Send + 'static> Wake for SlotWaker<T> {
+    fn wake(self: Arc<Self>) {
+        let Some(slot) = self.0.upgrade() else {
+            return;
+        };
+        let waker = slot.lock().waker.take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+static DEADLINE_TIMER: DeadlineTimer = DeadlineTimer {
+    wakers: Mutex::new(Vec::new()),
+    changed: Condvar::new(),
+};
+
+/// Wakes awaited [`RenderJob`]s when their deadline passes, on a thread
+/// shared by all pools, like [`RenderJob::wait`] does with `wait_timeout`.
+struct DeadlineTimer {
+    wakers: Mutex<Vec<(Instant, Waker)>>,
+    changed: Condvar,
+}
+
+impl DeadlineTimer {
+    fn wake_at(&'static self, deadline: Instant, waker: Waker) {
+        static STARTED: Once = Once::new();
+        STARTED.call_once(|| {
+            let _ = std::thread::Builder::new()
+                .name("charming-deadlines".to_string())
+                .spawn(|| self.run());
+        });
+
+        self.lock().push((deadline, waker));
+        self.changed.notify_one();
+    }
+
+    fn run(&self) {
+        let mut wakers = self.lock();
+        loop {
+            let now = Instant::now();
+            let (due, pending) = std::mem::take(&mut *wakers)
+                .into_iter()
+                .partition::<Vec<_>, _>(|(deadline, _)| *deadline <= now);
+            *wakers = pending;
+
+            // Waking locks the slot of the job, so it is done with the timer
+            // unlocked, to never hold both locks at once.
+            if !due.is_empty() {
+                drop(wakers);
+                for (_, waker) in due {
+                    waker.wake();
+                }
+                wakers = self.lock();
+                continue;
+            }
+
+            wakers = match wakers.iter().map(|(deadline, _)| *deadline).min() {
+                Some(next) => {
+                    self.changed
+                        .wait_timeout(wakers, next - now)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .changed
+                    .wait(wakers)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<(Instant, Waker)>> {
+        self.wakers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Drop for Reply<T> {
+    fn drop(&mut self) {
+        let state = self.0.lock();
+        if !state.closed {
+            self.close(state);
+        }
+    }
+}
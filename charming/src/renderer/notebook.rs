@@ -0,0 +1,118 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{Chart, EchartsError};
+
+use super::html_renderer::{ECHARTS_CDN, escape_script, register_maps, render_template};
+
+/// Height of charts shown in notebooks, which are as wide as the output area.
+const NOTEBOOK_HEIGHT: u32 = 400;
+/// Width of the SVG fallback of charts shown in notebooks.
+#[cfg(feature = "ssr")]
+const FALLBACK_WIDTH: u32 = 800;
+/// Time a chart may take to render its SVG fallback, so a runaway formatter
+/// does not hang the notebook.
+#[cfg(feature = "ssr")]
+const FALLBACK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// Heap limit of the renderer of SVG fallbacks.
+#[cfg(feature = "ssr")]
+const FALLBACK_HEAP_LIMIT: usize = 256 * 1024 * 1024;
+
+static NEXT_CHART: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(feature = "ssr")]
+enum Fallback {
+    Unstarted,
+    Ready(crate::ImageRenderer),
+    /// A fallback failed to render, later charts are shown without one
+    /// rather than failing again.
+    Disabled,
+}
+
+#[cfg(feature = "ssr")]
+thread_local! {
+    static FALLBACK: std::cell::RefCell<Fallback> =
+        const { std::cell::RefCell::new(Fallback::Unstarted) };
+}
+
+impl Chart {
+    /// Render the chart into an HTML fragment for notebooks, which loads
+    /// echarts from the CDN both in plain pages and in Jupyter's require.js
+    /// environment. Every fragment gets a unique element id, so a notebook
+    /// can show many charts.
+    #[cfg_attr(docsrs, doc(cfg(feature = "evcxr")))]
+    pub fn to_notebook_html(&self) -> Result<String, EchartsError> {
+        // The time keeps ids unique across kernel restarts, which reset the
+        // counter, as the outputs of earlier runs stay on the page.
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+        let id = format!(
+            "charming-{now:x}-{}",
+            NEXT_CHART.fetch_add(1, Ordering::Relaxed)
+        );
+
+        render_template(
+            include_str!("../asset/notebook.html.hbs"),
+            &serde_json::json!({
+                "chart_id": id,
+                "element_id": escape_script(&serde_json::Value::from(id.as_str()).to_string()),
+                "height": NOTEBOOK_HEIGHT,
+                "echarts_src": ECHARTS_CDN,
+                "echarts_module": ECHARTS_CDN.trim_end_matches(".js"),
+                "register_maps": register_maps(self),
+                "chart_option": escape_script(&self.to_json()?),
+            }),
+        )
+    }
+
+    /// Show the chart in an [evcxr](https://github.com/evcxr/evcxr) notebook,
+    /// which calls this method for the value of a cell.
+    ///
+    /// The interactive chart comes with a static SVG rendering when the `ssr`
+    /// feature is enabled, which notebook viewers that do not run scripts show
+    /// instead.
+    #[cfg_attr(docsrs, doc(cfg(feature = "evcxr")))]
+    pub fn evcxr_display(&self) {
+        match self.to_notebook_html() {
+            Ok(html) => print_content("text/html", &html),
+            Err(error) => print_content("text/plain", &error.to_string()),
+        }
+
+        #[cfg(feature = "ssr")]
+        if let Some(svg) = self.fallback_svg() {
+            print_content("image/svg+xml", &svg);
+        }
+    }
+
+    /// Render the chart with an image renderer kept for the notebook, as
+    /// starting V8 for every chart would be slow.
+    #[cfg(feature = "ssr")]
+    fn fallback_svg(&self) -> Option<String> {
+        FALLBACK.with(|fallback| {
+            let mut fallback = fallback.borrow_mut();
+            if let Fallback::Unstarted = *fallback {
+                *fallback = Fallback::Ready(
+                    crate::ImageRenderer::new(FALLBACK_WIDTH, NOTEBOOK_HEIGHT)
+                        .execution_timeout(FALLBACK_TIMEOUT)
+                        .heap_limit(FALLBACK_HEAP_LIMIT),
+                );
+            }
+            let Fallback::Ready(renderer) = &mut *fallback else {
+                return None;
+            };
+            let svg = renderer.render(self).ok();
+            if svg.is_none() {
+                *fallback = Fallback::Disabled;
+            }
+            svg
+        })
+    }
+}
+
+fn print_content(mime_type: &str, content: &str) {
+    println!("EVCXR_BEGIN_CONTENT {mime_type}\n{content}\nEVCXR_END_CONTENT");
+}
@@ -0,0 +1,314 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Sender},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{Chart, EchartsError, theme::Theme};
+
+use super::html_renderer::{ScriptSource, register_maps, render_template};
+
+static ECHARTS_PATH: &str = "echarts.min.js";
+
+/// How often the server thread checks whether the preview was dropped while
+/// no connections arrive.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long writing to a page may block before the page is dropped, so that
+/// a page which stopped reading does not hold up its event stream forever.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serves a chart on a local HTTP server for development, and updates the
+/// page whenever the chart changes.
+///
+/// The page fills the browser window and receives every chart passed to
+/// [`Preview::update`] through server-sent events, which are applied with
+/// `setOption` without reloading the page. The bundled echarts library is
+/// served together with the page, so no network access is needed.
+///
+/// ```no_run
+/// # use charming::{Chart, PreviewServer};
+/// let preview = PreviewServer::new("my chart")
+///     .start("127.0.0.1:8080", &Chart::new())
+///     .unwrap();
+/// println!("open {}", preview.url());
+/// preview.update(&Chart::new()).unwrap();
+/// ```
+pub struct PreviewServer {
+    title: String,
+    theme: Theme,
+    script_source: Option<ScriptSource>,
+}
+
+impl PreviewServer {
+    pub fn new<S: Into<String>>(title: S) -> Self {
+        Self {
+            title: title.into(),
+            theme: Theme::Default,
+            script_source: None,
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Set where the page loads echarts from, see [`ScriptSource`]. By
+    /// default the server serves the bundled echarts library itself.
+    pub fn script_source(mut self, script_source: ScriptSource) -> Self {
+        self.script_source = Some(script_source);
+        self
+    }
+
+    /// Start serving the chart on the given address, in background threads
+    /// which stop when the returned [`Preview`] is dropped. Use port `0` to
+    /// pick any free port, [`Preview::url`] tells which one.
+    pub fn start<A: ToSocketAddrs>(
+        self,
+        address: A,
+        chart: &Chart,
+    ) -> Result<Preview, EchartsError> {
+        let (theme, _) = self.theme.to_str();
+        let script_source = self
+            .script_source
+            .unwrap_or_else(|| ScriptSource::url(ECHARTS_PATH));
+        let page = render_template(
+            include_str!("../asset/preview.html.hbs"),
+            &serde_json::json!({
+                "title": self.title,
+                "theme": theme,
+                "scripts": script_source.scripts(&self.theme),
+            }),
+        )?;
+
+        let listener = TcpListener::bind(address)?;
+        // The server thread polls for connections, so that it notices when
+        // the preview is dropped without having to be woken up.
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(State {
+            page,
+            update: Mutex::new(update_script(chart)?),
+            clients: Mutex::new(Vec::new()),
+            stopped: AtomicBool::new(false),
+        });
+
+        let server = {
+            let state = Arc::clone(&state);
+            thread::spawn(move || {
+                while !state.stopped.load(Ordering::SeqCst) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            // Accepted streams inherit the non-blocking mode
+                            // of the listener on some platforms.
+                            if stream.set_nonblocking(false).is_ok() {
+                                let state = Arc::clone(&state);
+                                thread::spawn(move || handle(stream, &state));
+                            }
+                        }
+                        // Either no connection is pending, or an error like
+                        // running out of file descriptors, which may pass.
+                        Err(_) => thread::sleep(ACCEPT_INTERVAL),
+                    }
+                }
+            })
+        };
+
+        Ok(Preview {
+            address,
+            state,
+            server: Some(server),
+        })
+    }
+}
+
+/// A running [`PreviewServer`], which stops when dropped.
+pub struct Preview {
+    address: SocketAddr,
+    state: Arc<State>,
+    server: Option<JoinHandle<()>>,
+}
+
+impl Preview {
+    /// The address the server listens on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The URL of the page showing the chart.
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.local_address())
+    }
+
+    /// Show another chart on all open pages, and on pages opened later.
+    pub fn update(&self, chart: &Chart) -> Result<(), EchartsError> {
+        let update = update_script(chart)?;
+        let event: Arc<str> = event(&update).into();
+        *self
+            .state
+            .update
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = update;
+
+        // Each page is written to by its own thread, which stops when the
+        // page is closed, so sending never blocks on a slow page.
+        let mut clients = self
+            .state
+            .clients
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        clients.retain(|client| client.send(Arc::clone(&event)).is_ok());
+        Ok(())
+    }
+
+    /// The address to connect to, which is the loopback address when the
+    /// server listens on all interfaces.
+    fn local_address(&self) -> SocketAddr {
+        let mut address = self.address;
+        match address {
+            SocketAddr::V4(_) if address.ip().is_unspecified() => {
+                address.set_ip(Ipv4Addr::LOCALHOST.into())
+            }
+            SocketAddr::V6(_) if address.ip().is_unspecified() => {
+                address.set_ip(Ipv6Addr::LOCALHOST.into())
+            }
+            _ => {}
+        }
+        address
+    }
+}
+
+impl Drop for Preview {
+    fn drop(&mut self) {
+        self.state.stopped.store(true, Ordering::SeqCst);
+        if let Some(server) = self.server.take() {
+            let _ = server.join();
+        }
+        // Closing the channels stops the threads writing to the pages, which
+        // closes their event streams.
+        self.state
+            .clients
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+struct State {
+    page: String,
+    /// The script setting the option of the latest chart.
+    update: Mutex<String>,
+    /// The channels to the threads writing the event streams of the open
+    /// pages.
+    clients: Mutex<Vec<Sender<Arc<str>>>>,
+    stopped: AtomicBool,
+}
+
+fn handle(mut stream: TcpStream, state: &State) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let Some(path) = request_path(&stream) else {
+        return;
+    };
+
+    let _ = match path.as_str() {
+        "/" => respond(
+            &mut stream,
+            "200 OK",
+            "text/html; charset=utf-8",
+            &state.page,
+        ),
+        "/events" => stream_events(stream, state),
+        path if path == format!("/{ECHARTS_PATH}") => respond(
+            &mut stream,
+            "200 OK",
+            "text/javascript; charset=utf-8",
+            include_str!("../asset/echarts-5.5.1.min.js"),
+        ),
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "Not Found"),
+    };
+}
+
+/// Writes the latest chart and then every update to the page, until the page
+/// is closed, stops reading, or the preview is dropped.
+fn stream_events(mut stream: TcpStream, state: &State) -> std::io::Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let (sender, receiver) = mpsc::channel();
+    // Registering under the lock of the latest chart makes sure no update
+    // sent in between is missed.
+    let update = {
+        let update = state.update.lock().unwrap_or_else(PoisonError::into_inner);
+        state
+            .clients
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(sender);
+        event(&update)
+    };
+
+    let headers = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n";
+    stream.write_all(headers.as_bytes())?;
+    stream.write_all(update.as_bytes())?;
+    for event in receiver {
+        stream.write_all(event.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Reads the request head and returns the path of a `GET` request, without
+/// the query.
+fn request_path(stream: &TcpStream) -> Option<String> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    loop {
+        let mut header = String::new();
+        match reader.read_line(&mut header) {
+            Ok(0) => break,
+            Ok(_) if header.trim().is_empty() => break,
+            Ok(_) => {}
+            Err(_) => return None,
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    if parts.next() != Some("GET") {
+        return None;
+    }
+    let target = parts.next()?;
+    Some(target.split('?').next().unwrap_or(target).to_string())
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// The script a page runs to show a chart, with `echarts` and `chart` in
+/// scope.
+fn update_script(chart: &Chart) -> Result<String, EchartsError> {
+    let mut script = register_maps(chart).join("\n");
+    script.push_str(&format!("\nchart.setOption({}, true);", chart.to_json()?));
+    Ok(script)
+}
+
+/// A server-sent event carrying the data, line by line.
+fn event(data: &str) -> String {
+    let mut event: String = data.lines().map(|line| format!("data: {line}\n")).collect();
+    event.push('\n');
+    event
+}
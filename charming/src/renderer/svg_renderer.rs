@@ -0,0 +1,1115 @@
+use std::{collections::HashMap, f64::consts::PI, fmt::Write};
+
+use serde_json::Value;
+
+use super::chart_option::{
+    PALETTE, all, category_count, category_labels, chart_option, coordinate, first, format_number,
+    in_categories, item_value, length, num, number, parse_color, precision, text, tick_count,
+    value_extent,
+};
+use crate::{Chart, EchartsError};
+
+static VISUAL_MAP_COLORS: [&str; 3] = ["#f6efa6", "#d88273", "#bf444c"];
+
+/// Widths of the printable ASCII characters in a sans-serif font, the same
+/// table zrender uses to measure text: `(byte - 20) / 100` of the font size.
+static TEXT_WIDTHS: &[u8] =
+    br"007LLmW'55;N0500LLLLLLLLLL00NNNLzWW\\WQb\0FWLg\bWb\WQ\WrWWQ000CL5LLFLL0LL**F*gLLLL5F0LF\FFF5.5N";
+
+static SUPPORTED_SERIES: [&str; 5] = ["line", "bar", "scatter", "pie", "heatmap"];
+
+const AXIS_COLOR: &str = "#6E7079";
+const SPLIT_LINE_COLOR: &str = "#E0E6F1";
+const LABEL_COLOR: &str = "#333";
+
+/// Renders charts to SVG in pure Rust, without a JavaScript runtime.
+///
+/// `SvgRenderer` lays out the chart itself following the echarts defaults, so
+/// it works where V8 is not available and is much faster than the `ssr`
+/// renderers. It supports line, bar, scatter, pie and heatmap series on a
+/// single cartesian grid, together with titles and legends. Other components
+/// and most styling options are ignored, so the output is close to, but not
+/// the same as, what echarts renders. Charts with series of other types are
+/// rejected with an error. Charts with a timeline are rendered at its current
+/// frame, without the timeline itself.
+#[derive(Clone, Debug)]
+pub struct SvgRenderer {
+    width: u32,
+    height: u32,
+}
+
+impl SvgRenderer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    pub fn render(&self, chart: &Chart) -> Result<String, EchartsError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(EchartsError::InvalidSizeError(format!(
+                "{}x{} is not a valid chart size",
+                self.width, self.height
+            )));
+        }
+
+        let option = chart_option(chart)?;
+        let series = all(&option["series"]);
+        if let Some(unsupported) = series
+            .iter()
+            .map(|series| series["type"].as_str().unwrap_or_default())
+            .find(|type_| !SUPPORTED_SERIES.contains(type_))
+        {
+            return Err(EchartsError::ImageRenderingError(format!(
+                "series type `{unsupported}` is not supported by SvgRenderer"
+            )));
+        }
+
+        let mut palette: Vec<&str> = all(&option["color"])
+            .into_iter()
+            .filter_map(Value::as_str)
+            .collect();
+        if palette.is_empty() {
+            palette = PALETTE.to_vec();
+        }
+        let context = Context {
+            width: self.width as f64,
+            height: self.height as f64,
+            palette,
+        };
+
+        let mut svg = Svg::new(context.width, context.height);
+        if let Some(color) = text(&option["backgroundColor"]) {
+            svg.rect(0.0, 0.0, context.width, context.height, color);
+        }
+
+        if series.iter().any(|series| series["type"] != "pie") {
+            draw_cartesian(&mut svg, &context, &option, &series);
+        }
+        for series in series.iter().filter(|series| series["type"] == "pie") {
+            draw_pie(&mut svg, &context, series);
+        }
+        for title in all(&option["title"]) {
+            draw_title(&mut svg, &context, title);
+        }
+        for legend in all(&option["legend"]) {
+            draw_legend(&mut svg, &context, legend, &series);
+        }
+
+        Ok(svg.finish())
+    }
+
+    pub fn save<P: AsRef<std::path::Path>>(
+        &self,
+        chart: &Chart,
+        path: P,
+    ) -> Result<(), EchartsError> {
+        let svg = self.render(chart)?;
+        std::fs::write(path, svg).map_err(EchartsError::IoError)
+    }
+}
+
+struct Context<'a> {
+    width: f64,
+    height: f64,
+    palette: Vec<&'a str>,
+}
+
+impl Context<'_> {
+    fn color(&self, index: usize) -> &str {
+        self.palette[index % self.palette.len()]
+    }
+
+    fn series_color<'a>(&'a self, index: usize, series: &'a Value) -> &'a str {
+        text(&series["itemStyle"]["color"]).unwrap_or_else(|| self.color(index))
+    }
+}
+
+struct TextStyle<'a> {
+    size: f64,
+    color: &'a str,
+    anchor: &'a str,
+    bold: bool,
+}
+
+impl<'a> TextStyle<'a> {
+    fn new(size: f64, color: &'a str, anchor: &'a str) -> Self {
+        Self {
+            size,
+            color,
+            anchor,
+            bold: false,
+        }
+    }
+
+    /// Applies the `fontSize`, `color` and `fontWeight` of an echarts text
+    /// style option.
+    fn with_option(mut self, option: &'a Value) -> Self {
+        if let Some(size) = option["fontSize"].as_f64() {
+            self.size = size;
+        }
+        if let Some(color) = text(&option["color"]) {
+            self.color = color;
+        }
+        match &option["fontWeight"] {
+            Value::String(weight) => self.bold = weight == "bold" || weight == "bolder",
+            Value::Number(weight) => self.bold = weight.as_f64().is_some_and(|w| w >= 600.0),
+            _ => {}
+        }
+        self
+    }
+}
+
+struct Svg {
+    content: String,
+}
+
+impl Svg {
+    fn new(width: f64, height: f64) -> Self {
+        let mut content = String::new();
+        let _ = write!(
+            content,
+            r#"<svg width="{w}" height="{h}" xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" version="1.1" baseProfile="full" viewBox="0 0 {w} {h}">"#,
+            w = num(width),
+            h = num(height),
+        );
+        content.push('\n');
+        let _ = writeln!(
+            content,
+            r#"<rect width="{}" height="{}" x="0" y="0" fill="none"></rect>"#,
+            num(width),
+            num(height)
+        );
+        Self { content }
+    }
+
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str) {
+        let _ = writeln!(
+            self.content,
+            r#"<rect width="{}" height="{}" x="{}" y="{}" fill="{}"></rect>"#,
+            num(width),
+            num(height),
+            num(x),
+            num(y),
+            escape(fill)
+        );
+    }
+
+    fn fill(&mut self, path: &str, fill: &str, opacity: Option<f64>) {
+        let opacity = opacity
+            .map(|opacity| format!(r#" fill-opacity="{}""#, num(opacity)))
+            .unwrap_or_default();
+        let _ = writeln!(
+            self.content,
+            r#"<path d="{path}" fill="{}"{opacity}></path>"#,
+            escape(fill)
+        );
+    }
+
+    fn stroke(&mut self, path: &str, stroke: &str, width: f64) {
+        let _ = writeln!(
+            self.content,
+            r#"<path d="{path}" fill="none" stroke="{}" stroke-width="{}" stroke-linejoin="bevel"></path>"#,
+            escape(stroke),
+            num(width)
+        );
+    }
+
+    /// An empty circle, like the symbols of line series.
+    fn symbol(&mut self, x: f64, y: f64, radius: f64, stroke: &str) {
+        let _ = writeln!(
+            self.content,
+            r##"<path d="{}" fill="#fff" stroke="{}"></path>"##,
+            circle(x, y, radius),
+            escape(stroke)
+        );
+    }
+
+    fn text(&mut self, x: f64, y: f64, content: &str, style: &TextStyle) {
+        let weight = if style.bold { "font-weight:bold;" } else { "" };
+        let _ = writeln!(
+            self.content,
+            r#"<text dominant-baseline="central" text-anchor="{}" style="font-size:{}px;font-family:sans-serif;{weight}" transform="translate({} {})" fill="{}">{}</text>"#,
+            style.anchor,
+            num(style.size),
+            num(x),
+            num(y),
+            escape(style.color),
+            escape(content)
+        );
+    }
+
+    fn finish(mut self) -> String {
+        self.content.push_str("</svg>");
+        self.content
+    }
+}
+
+/// A point of a cartesian series in data coordinates, where category axes
+/// use the index of the category. `base` is where a stacked value starts.
+#[derive(Clone, Copy)]
+struct Point {
+    x: f64,
+    y: f64,
+    base: Option<f64>,
+}
+
+enum Scale {
+    Category {
+        labels: Vec<String>,
+        boundary_gap: bool,
+    },
+    Value {
+        min: f64,
+        max: f64,
+        interval: f64,
+    },
+}
+
+struct Axis<'a> {
+    option: &'a Value,
+    scale: Scale,
+    /// Pixel position of the start of the axis, the bottom of a vertical
+    /// axis.
+    start: f64,
+    end: f64,
+}
+
+impl Axis<'_> {
+    fn map(&self, value: f64) -> f64 {
+        let span = self.end - self.start;
+        match &self.scale {
+            Scale::Category {
+                labels,
+                boundary_gap: true,
+            } => self.start + span * (value + 0.5) / labels.len() as f64,
+            Scale::Category { labels, .. } if labels.len() > 1 => {
+                self.start + span * value / (labels.len() - 1) as f64
+            }
+            Scale::Category { .. } => self.start + span / 2.0,
+            Scale::Value { min, max, .. } => self.start + span * (value - min) / (max - min),
+        }
+    }
+
+    fn categories(&self) -> Option<&[String]> {
+        match &self.scale {
+            Scale::Category { labels, .. } => Some(labels),
+            Scale::Value { .. } => None,
+        }
+    }
+
+    /// Width of a category in pixels.
+    fn band(&self) -> f64 {
+        match &self.scale {
+            Scale::Category { labels, .. } => (self.end - self.start).abs() / labels.len() as f64,
+            Scale::Value { .. } => 0.0,
+        }
+    }
+
+    /// Clamps a value to the extent of a value axis.
+    fn clamp(&self, value: f64) -> f64 {
+        match self.scale {
+            Scale::Value { min, max, .. } => value.clamp(min, max),
+            Scale::Category { .. } => value,
+        }
+    }
+
+    fn shows(&self, component: &str, default: bool) -> bool {
+        self.option[component]["show"].as_bool().unwrap_or(default)
+    }
+
+    /// Pixel positions and texts of the axis labels.
+    fn labels(&self) -> Vec<(f64, String)> {
+        match &self.scale {
+            Scale::Category { labels, .. } => {
+                let step = match self.option["axisLabel"]["interval"].as_u64() {
+                    Some(interval) => interval as usize + 1,
+                    None => {
+                        let widest = labels
+                            .iter()
+                            .map(|label| text_width(label, 12.0))
+                            .fold(0.0, f64::max);
+                        let band = self.band().max(1.0);
+                        ((widest + 4.0) / band).ceil().max(1.0) as usize
+                    }
+                };
+                labels
+                    .iter()
+                    .enumerate()
+                    .step_by(step)
+                    .map(|(index, label)| (self.map(index as f64), label.clone()))
+                    .collect()
+            }
+            Scale::Value { min, max, interval } => {
+                let precision = precision(*interval);
+                let formatter = text(&self.option["axisLabel"]["formatter"]);
+                (0..=tick_count(*min, *max, *interval))
+                    .map(|step| {
+                        let value = min + interval * step as f64;
+                        let label = format_number(value, precision);
+                        let label = match formatter {
+                            Some(formatter) => formatter.replace("{value}", &label),
+                            None => label,
+                        };
+                        (self.map(value), label)
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Pixel positions of the ticks of a category axis, or of the split lines
+    /// of a value axis.
+    fn ticks(&self) -> Vec<f64> {
+        match &self.scale {
+            Scale::Category {
+                labels,
+                boundary_gap: true,
+            } => (0..=labels.len())
+                .map(|index| self.map(index as f64 - 0.5))
+                .collect(),
+            Scale::Category { labels, .. } => (0..labels.len())
+                .map(|index| self.map(index as f64))
+                .collect(),
+            Scale::Value { .. } => self.labels().into_iter().map(|(at, _)| at).collect(),
+        }
+    }
+}
+
+fn draw_cartesian(svg: &mut Svg, context: &Context, option: &Value, series: &[&Value]) {
+    let grid = first(&option["grid"]);
+    let left = length(&grid["left"], context.width).unwrap_or(context.width * 0.1);
+    let right =
+        context.width - length(&grid["right"], context.width).unwrap_or(context.width * 0.1);
+    let top = length(&grid["top"], context.height).unwrap_or(60.0);
+    let bottom = context.height - length(&grid["bottom"], context.height).unwrap_or(70.0);
+
+    let x_option = first(&option["xAxis"]);
+    let y_option = first(&option["yAxis"]);
+    let mut x_labels = category_labels(x_option);
+    let mut y_labels = category_labels(y_option);
+    let horizontal = y_labels.is_some() && x_labels.is_none();
+
+    // Parse the data of all cartesian series, stacking values on the value
+    // axis of the series with the same `stack`.
+    let mut stacks: HashMap<(String, i64, bool), f64> = HashMap::new();
+    let mut points: Vec<Vec<Option<Point>>> = Vec::new();
+    for series in series.iter().filter(|series| series["type"] != "pie") {
+        let data = all(&series["data"]);
+        let stack = match series["type"].as_str() {
+            Some("line" | "bar") => text(&series["stack"]),
+            _ => None,
+        };
+        let parsed = data
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let value = item_value(item);
+                let mut point = match value.as_array() {
+                    Some(values) => Point {
+                        x: coordinate(x_labels.as_deref(), values.first()?)?,
+                        y: coordinate(y_labels.as_deref(), values.get(1)?)?,
+                        base: None,
+                    },
+                    None if horizontal => Point {
+                        x: number(value)?,
+                        y: index as f64,
+                        base: None,
+                    },
+                    None => Point {
+                        x: index as f64,
+                        y: number(value)?,
+                        base: None,
+                    },
+                };
+                if !point.x.is_finite() || !point.y.is_finite() {
+                    return None;
+                }
+                if let Some(stack) = stack {
+                    let (category, value) = if horizontal {
+                        (point.y, &mut point.x)
+                    } else {
+                        (point.x, &mut point.y)
+                    };
+                    let sum = stacks
+                        .entry((stack.to_string(), category.round() as i64, *value >= 0.0))
+                        .or_insert(0.0);
+                    point.base = Some(*sum);
+                    *sum += *value;
+                    *value = *sum;
+                }
+                Some(point)
+            })
+            .collect();
+        points.push(parsed);
+    }
+
+    // Category axes get a category for every index a point is given at, and
+    // points outside the categories are left out.
+    let items = points.iter().map(Vec::len).max().unwrap_or(0);
+    if let Some(labels) = &mut x_labels {
+        let count = category_count(
+            labels,
+            points.iter().flatten().flatten().map(|p| p.x),
+            items,
+        );
+        pad_labels(labels, count);
+    }
+    if let Some(labels) = &mut y_labels {
+        let count = category_count(
+            labels,
+            points.iter().flatten().flatten().map(|p| p.y),
+            items,
+        );
+        pad_labels(labels, count);
+    }
+    for point in points.iter_mut().flatten() {
+        if point.as_ref().is_some_and(|point| {
+            !in_categories(x_labels.as_deref(), point.x)
+                || !in_categories(y_labels.as_deref(), point.y)
+        }) {
+            *point = None;
+        }
+    }
+
+    let x_scale = match x_labels {
+        Some(labels) => category_scale(x_option, labels),
+        None => value_scale(
+            x_option,
+            points.iter().flatten().flatten().flat_map(|point| {
+                [Some(point.x), point.base.filter(|_| horizontal)]
+                    .into_iter()
+                    .flatten()
+            }),
+        ),
+    };
+    let y_scale = match y_labels {
+        Some(labels) => category_scale(y_option, labels),
+        None => value_scale(
+            y_option,
+            points.iter().flatten().flatten().flat_map(|point| {
+                [Some(point.y), point.base.filter(|_| !horizontal)]
+                    .into_iter()
+                    .flatten()
+            }),
+        ),
+    };
+    let inverse = |option: &Value, start: f64, end: f64| {
+        if option["inverse"] == true {
+            (end, start)
+        } else {
+            (start, end)
+        }
+    };
+    let (start, end) = inverse(x_option, left, right);
+    let x_axis = Axis {
+        option: x_option,
+        scale: x_scale,
+        start,
+        end,
+    };
+    let (start, end) = inverse(y_option, bottom, top);
+    let y_axis = Axis {
+        option: y_option,
+        scale: y_scale,
+        start,
+        end,
+    };
+
+    draw_axis(svg, &x_axis, &y_axis, true);
+    draw_axis(svg, &y_axis, &x_axis, false);
+
+    let (base_axis, value_axis) = if horizontal {
+        (&y_axis, &x_axis)
+    } else {
+        (&x_axis, &y_axis)
+    };
+    let cartesian: Vec<(usize, &Value)> = series
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, series)| series["type"] != "pie")
+        .collect();
+    let bars = bar_layout(&cartesian, base_axis.band());
+    let zero = value_axis.clamp(0.0);
+
+    for ((index, series), points) in cartesian.iter().zip(&points) {
+        let color = context.series_color(*index, series);
+        let pixel = |point: &Point| (x_axis.map(point.x), y_axis.map(point.y));
+        let base_pixel = |point: &Point| {
+            let base = value_axis.map(value_axis.clamp(point.base.unwrap_or(zero)));
+            if horizontal {
+                (base, y_axis.map(point.y))
+            } else {
+                (x_axis.map(point.x), base)
+            }
+        };
+
+        match series["type"].as_str() {
+            Some("line") => {
+                let segments: Vec<Vec<&Point>> = points
+                    .split(Option::is_none)
+                    .map(|segment| segment.iter().flatten().collect())
+                    .filter(|segment: &Vec<&Point>| !segment.is_empty())
+                    .collect();
+                if !series["areaStyle"].is_null() {
+                    let area_color = text(&series["areaStyle"]["color"]).unwrap_or(color);
+                    let opacity = series["areaStyle"]["opacity"].as_f64().unwrap_or(0.7);
+                    for segment in &segments {
+                        let top = segment.iter().map(|point| pixel(point));
+                        let bottom = segment.iter().rev().map(|point| base_pixel(point));
+                        let mut path = polyline(top.chain(bottom));
+                        path.push('Z');
+                        svg.fill(&path, area_color, Some(opacity));
+                    }
+                }
+                let line_color = text(&series["lineStyle"]["color"]).unwrap_or(color);
+                let line_width = series["lineStyle"]["width"].as_f64().unwrap_or(2.0);
+                for segment in &segments {
+                    svg.stroke(
+                        &polyline(segment.iter().map(|point| pixel(point))),
+                        line_color,
+                        line_width,
+                    );
+                }
+                if series["showSymbol"] != false && series["symbol"] != "none" {
+                    let radius = series["symbolSize"].as_f64().unwrap_or(4.0) / 2.0;
+                    for point in segments.iter().flatten() {
+                        let (x, y) = pixel(point);
+                        svg.symbol(x, y, radius, color);
+                    }
+                }
+            }
+            Some("bar") => {
+                let (offset, width) = bars[&bar_stack(*index, series)];
+                for point in points.iter().flatten() {
+                    let (x, y) = pixel(point);
+                    let (base_x, base_y) = base_pixel(point);
+                    let path = if horizontal {
+                        let y = base_axis.map(point.y) + offset;
+                        format!(
+                            "M{} {}l{} 0l0 {}l{} 0Z",
+                            num(base_x),
+                            num(y),
+                            num(x - base_x),
+                            num(width),
+                            num(base_x - x)
+                        )
+                    } else {
+                        let x = base_axis.map(point.x) + offset;
+                        format!(
+                            "M{} {}l{} 0l0 {}l{} 0Z",
+                            num(x),
+                            num(base_y),
+                            num(width),
+                            num(y - base_y),
+                            num(-width)
+                        )
+                    };
+                    svg.fill(&path, color, None);
+                }
+            }
+            Some("scatter") => {
+                let radius = series["symbolSize"].as_f64().unwrap_or(10.0) / 2.0;
+                let opacity = series["itemStyle"]["opacity"].as_f64().unwrap_or(0.8);
+                for point in points.iter().flatten() {
+                    let (x, y) = pixel(point);
+                    svg.fill(&circle(x, y, radius), color, Some(opacity));
+                }
+            }
+            Some("heatmap") => {
+                let visual_map = first(&option["visualMap"]);
+                let width = x_axis.band();
+                let height = y_axis.band();
+                for item in all(&series["data"]) {
+                    let cell = || {
+                        let values = item_value(item).as_array()?;
+                        Some((
+                            coordinate(x_axis.categories(), values.first()?)?,
+                            coordinate(y_axis.categories(), values.get(1)?)?,
+                            number(values.get(2)?)?,
+                        ))
+                    };
+                    if let Some((x, y, value)) = cell() {
+                        let fill = visual_color(visual_map, value);
+                        let (x, y) = (x_axis.map(x), y_axis.map(y));
+                        svg.rect(x - width / 2.0, y - height / 2.0, width, height, &fill);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn draw_axis(svg: &mut Svg, axis: &Axis, other: &Axis, horizontal: bool) {
+    if axis.option["show"] == false {
+        return;
+    }
+    let is_category = matches!(axis.scale, Scale::Category { .. });
+    let (low, high) = (other.start.min(other.end), other.start.max(other.end));
+    // Horizontal axes sit at the bottom of the grid, vertical axes at its left.
+    let edge = if horizontal { high } else { low };
+    let snap = |position: f64| position.round() + 0.5;
+
+    if axis.shows("splitLine", !is_category) {
+        for at in axis.ticks() {
+            let at = snap(at);
+            let path = if horizontal {
+                format!("M{} {}L{} {}", num(at), num(low), num(at), num(high))
+            } else {
+                format!("M{} {}L{} {}", num(low), num(at), num(high), num(at))
+            };
+            svg.stroke(&path, SPLIT_LINE_COLOR, 1.0);
+        }
+    }
+
+    if axis.shows("axisLine", is_category) {
+        let edge = snap(edge);
+        let path = if horizontal {
+            format!(
+                "M{} {}L{} {}",
+                num(axis.start),
+                num(edge),
+                num(axis.end),
+                num(edge)
+            )
+        } else {
+            format!(
+                "M{} {}L{} {}",
+                num(edge),
+                num(axis.start),
+                num(edge),
+                num(axis.end)
+            )
+        };
+        svg.stroke(&path, AXIS_COLOR, 1.0);
+    }
+
+    if axis.shows("axisTick", is_category) {
+        for at in axis.ticks() {
+            let at = snap(at);
+            let path = if horizontal {
+                format!("M{} {}L{} {}", num(at), num(edge), num(at), num(edge + 5.0))
+            } else {
+                format!("M{} {}L{} {}", num(edge), num(at), num(edge - 5.0), num(at))
+            };
+            svg.stroke(&path, AXIS_COLOR, 1.0);
+        }
+    }
+
+    if axis.shows("axisLabel", true) {
+        let anchor = if horizontal { "middle" } else { "end" };
+        let style = TextStyle::new(12.0, AXIS_COLOR, anchor).with_option(&axis.option["axisLabel"]);
+        for (at, label) in axis.labels() {
+            if horizontal {
+                svg.text(at, edge + 8.0 + style.size / 2.0, &label, &style);
+            } else {
+                svg.text(edge - 8.0, at, &label, &style);
+            }
+        }
+    }
+}
+
+/// The offset from the category center and the width of the bars of each
+/// stack, laid out like echarts does with `barGap` and `barCategoryGap`.
+fn bar_layout(series: &[(usize, &Value)], band: f64) -> HashMap<String, (f64, f64)> {
+    let mut stacks: Vec<(String, Option<f64>)> = Vec::new();
+    let mut gap = 0.2;
+    let mut category_gap = None;
+    for (index, series) in series {
+        if series["type"] != "bar" {
+            continue;
+        }
+        let stack = bar_stack(*index, series);
+        if !stacks.iter().any(|(name, _)| *name == stack) {
+            stacks.push((stack, length(&series["barWidth"], band)));
+        }
+        if let Some(bar_gap) = length(&series["barGap"], 1.0) {
+            gap = bar_gap;
+        }
+        if let Some(gap) = length(&series["barCategoryGap"], band) {
+            category_gap = Some(gap);
+        }
+    }
+
+    let category_gap =
+        category_gap.unwrap_or_else(|| (35.0 - 4.0 * stacks.len() as f64).max(15.0) / 100.0 * band);
+    let fixed: Vec<f64> = stacks.iter().filter_map(|(_, width)| *width).collect();
+    let remaining = band
+        - fixed.iter().sum::<f64>()
+        - fixed.iter().map(|width| width * (1.0 + gap)).sum::<f64>();
+    let count = (stacks.len() - fixed.len()) as f64;
+    let auto = ((remaining - category_gap) / (count + (count - 1.0) * gap)).max(0.0);
+
+    let widths: Vec<f64> = stacks
+        .iter()
+        .map(|(_, width)| width.unwrap_or(auto))
+        .collect();
+    let total = widths.iter().map(|width| width * (1.0 + gap)).sum::<f64>()
+        - widths.last().map_or(0.0, |width| width * gap);
+    let mut offset = -total / 2.0;
+    stacks
+        .into_iter()
+        .zip(widths)
+        .map(|((stack, _), width)| {
+            let layout = (offset, width);
+            offset += width * (1.0 + gap);
+            (stack, layout)
+        })
+        .collect()
+}
+
+fn bar_stack(index: usize, series: &Value) -> String {
+    match text(&series["stack"]) {
+        Some(stack) => format!("stack:{stack}"),
+        None => format!("series:{index}"),
+    }
+}
+
+/// A value scale covering the values.
+fn value_scale(axis: &Value, values: impl Iterator<Item = f64>) -> Scale {
+    let (min, max, interval) = value_extent(axis, values);
+    Scale::Value { min, max, interval }
+}
+
+fn pad_labels(labels: &mut Vec<String>, count: usize) {
+    while labels.len() < count.max(1) {
+        labels.push(labels.len().to_string());
+    }
+}
+
+fn category_scale(axis: &Value, labels: Vec<String>) -> Scale {
+    Scale::Category {
+        labels,
+        boundary_gap: axis["boundaryGap"] != false,
+    }
+}
+
+/// The color of a value on the continuous scale of a visual map.
+fn visual_color(visual_map: &Value, value: f64) -> String {
+    let colors: Vec<&str> = all(&visual_map["inRange"]["color"])
+        .into_iter()
+        .filter_map(Value::as_str)
+        .collect();
+    let colors = if colors.is_empty() {
+        VISUAL_MAP_COLORS.to_vec()
+    } else {
+        colors
+    };
+    let min = visual_map["min"].as_f64().unwrap_or(0.0);
+    let max = visual_map["max"].as_f64().unwrap_or(200.0);
+    let ratio = if max > min {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let position = ratio * (colors.len() - 1) as f64;
+    let index = (position.floor() as usize).min(colors.len().saturating_sub(2));
+    match (
+        parse_color(colors[index]),
+        colors.get(index + 1).and_then(|c| parse_color(c)),
+    ) {
+        (Some(from), Some(to)) => {
+            let t = position - index as f64;
+            let channel = |i: usize| (from[i] + (to[i] - from[i]) * t).round();
+            format!("rgb({},{},{})", channel(0), channel(1), channel(2))
+        }
+        _ => colors[index].to_string(),
+    }
+}
+
+fn draw_pie(svg: &mut Svg, context: &Context, series: &Value) {
+    let center = all(&series["center"]);
+    let cx = center
+        .first()
+        .and_then(|x| length(x, context.width))
+        .unwrap_or(context.width / 2.0);
+    let cy = center
+        .get(1)
+        .and_then(|y| length(y, context.height))
+        .unwrap_or(context.height / 2.0);
+    let size = context.width.min(context.height) / 2.0;
+    let (inner, outer) = match &series["radius"] {
+        Value::Array(radius) => (
+            radius.first().and_then(|r| length(r, size)).unwrap_or(0.0),
+            radius
+                .get(1)
+                .and_then(|r| length(r, size))
+                .unwrap_or(size * 0.75),
+        ),
+        radius => (0.0, length(radius, size).unwrap_or(size * 0.75)),
+    };
+
+    let data = all(&series["data"]);
+    let values: Vec<f64> = data
+        .iter()
+        .map(|item| number(item_value(item)).unwrap_or(0.0).max(0.0))
+        .collect();
+    let sum: f64 = values.iter().sum();
+    if sum <= 0.0 {
+        return;
+    }
+
+    let direction = if series["clockwise"] == false {
+        1.0
+    } else {
+        -1.0
+    };
+    let mut angle = series["startAngle"].as_f64().unwrap_or(90.0).to_radians();
+    let point = |radius: f64, angle: f64| (cx + radius * angle.cos(), cy - radius * angle.sin());
+    let sweep = if direction < 0.0 { 1 } else { 0 };
+    let arc = |path: &mut String, radius: f64, from: f64, to: f64, sweep: i32| {
+        // A full circle is split in two arcs, as one arc cannot end where it starts.
+        let span = (to - from).abs();
+        let steps = if span >= 2.0 * PI - 1e-6 { 2 } else { 1 };
+        for step in 1..=steps {
+            let (x, y) = point(radius, from + (to - from) * step as f64 / steps as f64);
+            let large = i32::from(span / steps as f64 > PI);
+            let _ = write!(
+                path,
+                "A{r} {r} 0 {large} {sweep} {} {}",
+                num(x),
+                num(y),
+                r = num(radius)
+            );
+        }
+    };
+
+    let label_shown = series["label"]["show"] != false;
+    let inside = matches!(
+        text(&series["label"]["position"]),
+        Some("inside" | "inner" | "center")
+    );
+    for (index, (item, value)) in data.iter().zip(&values).enumerate() {
+        let span = value / sum * 2.0 * PI * direction;
+        let (from, to) = (angle, angle + span);
+        angle = to;
+        if *value == 0.0 {
+            continue;
+        }
+        let color = text(&item["itemStyle"]["color"]).unwrap_or_else(|| context.color(index));
+
+        let (x, y) = point(outer, from);
+        let mut path = format!("M{} {}", num(x), num(y));
+        arc(&mut path, outer, from, to, sweep);
+        if inner > 0.0 {
+            let (x, y) = point(inner, to);
+            let _ = write!(path, "L{} {}", num(x), num(y));
+            arc(&mut path, inner, to, from, 1 - sweep);
+        } else {
+            let _ = write!(path, "L{} {}", num(cx), num(cy));
+        }
+        path.push('Z');
+        svg.fill(&path, color, None);
+
+        let name = text(&item["name"]).unwrap_or_default();
+        if !label_shown || name.is_empty() {
+            continue;
+        }
+        let middle = (from + to) / 2.0;
+        if inside {
+            let (x, y) = point((inner + outer) / 2.0, middle);
+            let style = TextStyle::new(12.0, "#fff", "middle");
+            svg.text(x, y, name, &style);
+        } else {
+            let side = if middle.cos() >= 0.0 { 1.0 } else { -1.0 };
+            let start = point(outer, middle);
+            let (x, y) = point(outer + 15.0, middle);
+            let end = (x + side * 15.0, y);
+            svg.stroke(&polyline([start, (x, y), end].into_iter()), color, 1.0);
+            let anchor = if side > 0.0 { "start" } else { "end" };
+            let style = TextStyle::new(12.0, LABEL_COLOR, anchor);
+            svg.text(end.0 + side * 5.0, end.1, name, &style);
+        }
+    }
+}
+
+fn draw_title(svg: &mut Svg, context: &Context, title: &Value) {
+    if title["show"] == false {
+        return;
+    }
+    let main = text(&title["text"]).unwrap_or_default();
+    let sub = text(&title["subtext"]).unwrap_or_default();
+    let main_style = TextStyle {
+        bold: true,
+        ..TextStyle::new(18.0, "#464646", "start")
+    }
+    .with_option(&title["textStyle"]);
+    let sub_style = TextStyle::new(12.0, AXIS_COLOR, "start").with_option(&title["subtextStyle"]);
+
+    let mut heights = Vec::new();
+    if !main.is_empty() {
+        heights.push(main_style.size);
+    }
+    if !sub.is_empty() {
+        heights.push(sub_style.size);
+    }
+    if heights.is_empty() {
+        return;
+    }
+    let gap = title["itemGap"].as_f64().unwrap_or(10.0);
+    let width = text_width(main, main_style.size).max(text_width(sub, sub_style.size));
+    let height = heights.iter().sum::<f64>() + gap * (heights.len() - 1) as f64;
+
+    let (x, anchor) = match text(&title["left"]) {
+        Some("center") => (context.width / 2.0, "middle"),
+        Some("right") => (context.width - 5.0, "end"),
+        _ => match place(&title["left"], &title["right"], context.width, width) {
+            Some(x) => (x, "start"),
+            None => (5.0, "start"),
+        },
+    };
+    let mut y = place(&title["top"], &title["bottom"], context.height, height).unwrap_or(5.0);
+
+    for (content, style) in [(main, main_style), (sub, sub_style)] {
+        if content.is_empty() {
+            continue;
+        }
+        let style = TextStyle { anchor, ..style };
+        svg.text(x, y + style.size / 2.0, content, &style);
+        y += style.size + gap;
+    }
+}
+
+fn draw_legend(svg: &mut Svg, context: &Context, legend: &Value, series: &[&Value]) {
+    if legend["show"] == false {
+        return;
+    }
+
+    // Every legend item is named after a series, or a data item of a pie.
+    let mut entries: Vec<(String, &str, bool)> = Vec::new();
+    for (index, series) in series.iter().enumerate() {
+        if series["type"] == "pie" {
+            for (index, item) in all(&series["data"]).into_iter().enumerate() {
+                if let Some(name) = text(&item["name"]) {
+                    let color =
+                        text(&item["itemStyle"]["color"]).unwrap_or_else(|| context.color(index));
+                    entries.push((name.to_string(), color, false));
+                }
+            }
+        } else if let Some(name) = text(&series["name"]) {
+            let color = context.series_color(index, series);
+            entries.push((name.to_string(), color, series["type"] == "line"));
+        }
+    }
+    let names: Vec<String> = match legend["data"].as_array() {
+        Some(data) => data
+            .iter()
+            .filter_map(|item| text(item).or_else(|| text(&item["name"])))
+            .map(String::from)
+            .collect(),
+        None => entries.iter().map(|(name, ..)| name.clone()).collect(),
+    };
+    let mut items: Vec<(String, &str, bool)> = Vec::new();
+    for name in names {
+        if items.iter().any(|(existing, ..)| *existing == name) {
+            continue;
+        }
+        if let Some((_, color, line)) = entries.iter().find(|(entry, ..)| *entry == name) {
+            items.push((name, color, *line));
+        }
+    }
+    if items.is_empty() {
+        return;
+    }
+
+    let style = TextStyle::new(12.0, LABEL_COLOR, "start").with_option(&legend["textStyle"]);
+    let gap = legend["itemGap"].as_f64().unwrap_or(10.0);
+    let widths: Vec<f64> = items
+        .iter()
+        .map(|(name, ..)| 30.0 + text_width(name, style.size))
+        .collect();
+    let vertical = legend["orient"] == "vertical";
+    let (width, height) = if vertical {
+        (
+            widths.iter().copied().fold(0.0, f64::max),
+            14.0 * items.len() as f64 + gap * (items.len() - 1) as f64,
+        )
+    } else {
+        (
+            widths.iter().sum::<f64>() + gap * (items.len() - 1) as f64,
+            14.0,
+        )
+    };
+
+    let mut x = place(&legend["left"], &legend["right"], context.width, width)
+        .unwrap_or((context.width - width) / 2.0);
+    let mut y = place(&legend["top"], &legend["bottom"], context.height, height).unwrap_or(5.0);
+
+    for ((name, color, line), item_width) in items.iter().zip(widths) {
+        let _ = writeln!(
+            svg.content,
+            r#"<g transform="translate({} {})">"#,
+            num(x),
+            num(y)
+        );
+        if *line {
+            svg.stroke("M0 7L25 7", color, 2.0);
+            svg.symbol(12.5, 7.0, 5.6, color);
+        } else {
+            svg.fill(
+                "M3.5 0L21.5 0A3.5 3.5 0 0 1 25 3.5L25 10.5A3.5 3.5 0 0 1 21.5 14L3.5 14A3.5 3.5 0 0 1 0 10.5L0 3.5A3.5 3.5 0 0 1 3.5 0",
+                color,
+                None,
+            );
+        }
+        svg.text(30.0, 7.0, name, &style);
+        svg.content.push_str("</g>\n");
+
+        if vertical {
+            y += 14.0 + gap;
+        } else {
+            x += item_width + gap;
+        }
+    }
+}
+
+/// Resolves the `left`/`right` or `top`/`bottom` position of a box of the
+/// given size.
+fn place(start: &Value, end: &Value, total: f64, size: f64) -> Option<f64> {
+    match text(start) {
+        Some("left" | "top") => return Some(5.0),
+        Some("center" | "middle") => return Some((total - size) / 2.0),
+        Some("right" | "bottom") => return Some(total - 5.0 - size),
+        _ => {}
+    }
+    length(start, total).or_else(|| length(end, total).map(|end| total - end - size))
+}
+
+fn circle(x: f64, y: f64, radius: f64) -> String {
+    format!(
+        "M{} {}A{r} {r} 0 1 1 {} {}A{r} {r} 0 1 1 {} {}Z",
+        num(x + radius),
+        num(y),
+        num(x - radius),
+        num(y),
+        num(x + radius),
+        num(y),
+        r = num(radius)
+    )
+}
+
+fn polyline(points: impl Iterator<Item = (f64, f64)>) -> String {
+    let mut path = String::new();
+    for (index, (x, y)) in points.enumerate() {
+        let command = if index == 0 { 'M' } else { 'L' };
+        let _ = write!(path, "{command}{} {}", num(x), num(y));
+    }
+    path
+}
+
+/// Width of a text in pixels, measured like zrender does without a canvas.
+fn text_width(text: &str, size: f64) -> f64 {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => f64::from(TEXT_WIDTHS[c as usize - 32] - 20) / 100.0 * size,
+            _ => size,
+        })
+        .sum()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
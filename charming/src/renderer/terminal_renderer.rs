@@ -0,0 +1,653 @@
+use std::io::Write;
+
+use serde_json::Value;
+
+use super::chart_option::{
+    PALETTE, all, category_count, category_labels, chart_option, coordinate, first, format_number,
+    in_categories, item_value, number, parse_color, precision, text, tick_count, value_extent,
+};
+use crate::{Chart, EchartsError};
+
+static SUPPORTED_SERIES: [&str; 3] = ["line", "bar", "scatter"];
+
+/// Bits of the dots of a braille character, indexed by `[y][x]` within the
+/// 2x4 dots of a character cell.
+const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Draws charts as text into a terminal, for previews without a browser,
+/// e.g. over SSH.
+///
+/// Line, bar and scatter series are plotted with braille characters, which
+/// have 2x4 dots per character, on a category or value x axis and a value y
+/// axis. Titles and legends are written as plain text, other components and
+/// styling options are ignored. Charts with a timeline are drawn at its
+/// current frame. Series are colored with ANSI escape codes unless colors are
+/// disabled.
+///
+/// To show the chart as an image in terminals that support it, render it with
+/// `ImageRenderer::render_terminal` instead, which needs the `ssr-raster`
+/// feature.
+#[derive(Clone, Debug)]
+pub struct TerminalRenderer {
+    columns: u32,
+    rows: u32,
+    colors: bool,
+}
+
+impl TerminalRenderer {
+    /// A renderer drawing charts of the given number of character columns
+    /// and rows.
+    pub fn new(columns: u32, rows: u32) -> Self {
+        Self {
+            columns,
+            rows,
+            colors: true,
+        }
+    }
+
+    /// Whether series are colored with ANSI escape codes, defaults to `true`.
+    pub fn colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Render a chart as lines of text.
+    pub fn render(&self, chart: &Chart) -> Result<String, EchartsError> {
+        let option = chart_option(chart)?;
+        let series = all(&option["series"]);
+        if let Some(unsupported) = series
+            .iter()
+            .map(|series| series["type"].as_str().unwrap_or_default())
+            .find(|type_| !SUPPORTED_SERIES.contains(type_))
+        {
+            return Err(EchartsError::ImageRenderingError(format!(
+                "series type `{unsupported}` is not supported by TerminalRenderer"
+            )));
+        }
+        let y_option = first(&option["yAxis"]);
+        if category_labels(y_option).is_some() {
+            return Err(EchartsError::ImageRenderingError(
+                "category y axes are not supported by TerminalRenderer".to_string(),
+            ));
+        }
+
+        let mut palette: Vec<&str> = all(&option["color"])
+            .into_iter()
+            .filter_map(Value::as_str)
+            .collect();
+        if palette.is_empty() {
+            palette = PALETTE.to_vec();
+        }
+        let colors: Vec<Option<[f64; 3]>> = series
+            .iter()
+            .enumerate()
+            .map(|(index, series)| {
+                let color =
+                    text(&series["itemStyle"]["color"]).unwrap_or(palette[index % palette.len()]);
+                parse_color(color).filter(|_| self.colors)
+            })
+            .collect();
+
+        let title = all(&option["title"])
+            .into_iter()
+            .find(|title| title["show"] != false)
+            .and_then(|title| text(&title["text"]))
+            .filter(|title| !title.is_empty());
+        let legend: Vec<(usize, &str)> = series
+            .iter()
+            .enumerate()
+            .filter_map(|(index, series)| Some((index, text(&series["name"])?)))
+            .collect();
+        let show_legend = !legend.is_empty()
+            && (!option["legend"].is_null() || legend.len() > 1)
+            && first(&option["legend"])["show"] != false;
+
+        let reserved = 2 + u32::from(title.is_some()) + u32::from(show_legend);
+        let plot_rows = self.rows.saturating_sub(reserved) as usize;
+        if plot_rows < 2 {
+            return Err(EchartsError::InvalidSizeError(format!(
+                "{} rows are too few for a chart",
+                self.rows
+            )));
+        }
+
+        // Parse the series data, category axes use the index of the category.
+        let x_option = first(&option["xAxis"]);
+        let x_labels = category_labels(x_option);
+        let mut points: Vec<Vec<Option<(f64, f64)>>> = series
+            .iter()
+            .map(|series| {
+                all(&series["data"])
+                    .iter()
+                    .enumerate()
+                    .map(|(index, item)| {
+                        let value = item_value(item);
+                        let point = match value.as_array() {
+                            Some(values) => (
+                                coordinate(x_labels.as_deref(), values.first()?)?,
+                                number(values.get(1)?)?,
+                            ),
+                            None => (index as f64, number(value)?),
+                        };
+                        (point.0.is_finite() && point.1.is_finite()).then_some(point)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Category axes get a category for every index a point is given at,
+        // and points outside the categories are left out.
+        let x_count = x_labels.as_deref().map(|labels| {
+            let items = points.iter().map(Vec::len).max().unwrap_or(0);
+            category_count(
+                labels,
+                points.iter().flatten().flatten().map(|(x, _)| *x),
+                items,
+            )
+        });
+        for point in points.iter_mut().flatten() {
+            if point.is_some_and(|(x, _)| !in_categories(x_labels.as_deref(), x)) {
+                *point = None;
+            }
+        }
+        let values = || points.iter().flatten().flatten();
+
+        let (y_min, y_max, y_interval) = value_extent(y_option, values().map(|(_, y)| *y));
+        let y_precision = precision(y_interval);
+        let y_ticks: Vec<(f64, String)> = (0..=tick_count(y_min, y_max, y_interval))
+            .map(|step| {
+                let value = y_min + y_interval * step as f64;
+                (value, format_number(value, y_precision))
+            })
+            .collect();
+        let label_width = y_ticks
+            .iter()
+            .map(|(_, label)| label.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let plot_columns = (self.columns as usize).saturating_sub(label_width + 1);
+        if plot_columns < 2 {
+            return Err(EchartsError::InvalidSizeError(format!(
+                "{} columns are too few for a chart",
+                self.columns
+            )));
+        }
+        let mut canvas = Canvas::new(plot_columns, plot_rows);
+        let (width, height) = (canvas.width() as f64, canvas.height() as f64);
+
+        let x_scale = match x_count {
+            Some(count) => XScale::Category {
+                count: count.max(1),
+                boundary_gap: x_option["boundaryGap"] != false,
+            },
+            None => {
+                let (min, max, interval) = value_extent(x_option, values().map(|(x, _)| *x));
+                XScale::Value { min, max, interval }
+            }
+        };
+        let map_x = |x: f64| x_scale.map(x, width);
+        let map_y = |y: f64| (height - 1.0) * (1.0 - (y - y_min) / (y_max - y_min));
+
+        let bar_series: Vec<usize> = series
+            .iter()
+            .enumerate()
+            .filter(|(_, series)| series["type"] == "bar")
+            .map(|(index, _)| index)
+            .collect();
+        let band = x_scale.band(width);
+        let bar_width = (band * 0.7 / bar_series.len().max(1) as f64)
+            .floor()
+            .max(1.0);
+        let zero = map_y(0.0_f64.clamp(y_min, y_max));
+
+        for (index, (series, points)) in series.iter().zip(&points).enumerate() {
+            match series["type"].as_str() {
+                Some("line") => {
+                    for pair in points.windows(2) {
+                        if let [Some(from), Some(to)] = pair {
+                            canvas.line(
+                                (map_x(from.0), map_y(from.1)),
+                                (map_x(to.0), map_y(to.1)),
+                                index,
+                            );
+                        }
+                    }
+                    for (x, y) in points.iter().flatten() {
+                        canvas.set(map_x(*x), map_y(*y), index);
+                    }
+                }
+                Some("bar") => {
+                    let position = bar_series.iter().position(|bar| *bar == index);
+                    let offset = (position.unwrap_or(0) as f64
+                        - (bar_series.len() as f64 - 1.0) / 2.0)
+                        * bar_width
+                        - bar_width / 2.0;
+                    for (x, y) in points.iter().flatten() {
+                        let start = (map_x(*x) + offset).round();
+                        let (top, bottom) = (map_y(*y).min(zero), map_y(*y).max(zero));
+                        for dot_x in 0..bar_width as usize {
+                            canvas.line(
+                                (start + dot_x as f64, top),
+                                (start + dot_x as f64, bottom),
+                                index,
+                            );
+                        }
+                    }
+                }
+                _ => {
+                    for (x, y) in points.iter().flatten() {
+                        canvas.set(map_x(*x), map_y(*y), index);
+                    }
+                }
+            }
+        }
+
+        let mut lines = Vec::new();
+        if let Some(title) = title {
+            lines.push(title.to_string());
+        }
+
+        // Every tick label goes to the row of its dot, the plot follows the
+        // y axis.
+        let mut tick_rows = vec![None; plot_rows];
+        for (value, label) in &y_ticks {
+            let row = (map_y(*value).round() as usize / 4).min(plot_rows - 1);
+            tick_rows[row] = Some(label.as_str());
+        }
+        for (row, tick) in tick_rows.iter().enumerate() {
+            let (label, axis) = match tick {
+                Some(label) => (*label, '┤'),
+                None => ("", '│'),
+            };
+            let mut line = format!("{label:>label_width$}{axis}");
+            canvas.write_row(&mut line, row, &colors);
+            lines.push(line.trim_end().to_string());
+        }
+        lines.push(format!("{:label_width$}└{}", "", "─".repeat(plot_columns)));
+
+        // Axis labels are centered below their position and skipped where
+        // they would overlap the previous one.
+        let x_ticks: Vec<(f64, String)> = match (&x_scale, &x_labels) {
+            (XScale::Category { count, .. }, labels) => (0..*count)
+                .map(|index| {
+                    let label = labels
+                        .as_ref()
+                        .and_then(|labels| labels.get(index).cloned())
+                        .unwrap_or_else(|| index.to_string());
+                    (map_x(index as f64), label)
+                })
+                .collect(),
+            (XScale::Value { min, max, interval }, _) => (0..=tick_count(*min, *max, *interval))
+                .map(|step| {
+                    let value = min + interval * step as f64;
+                    (map_x(value), format_number(value, precision(*interval)))
+                })
+                .collect(),
+        };
+        let mut labels: Vec<char> = vec![' '; label_width + 1 + plot_columns];
+        let mut free = 0;
+        for (x, label) in x_ticks {
+            let chars: Vec<char> = label.chars().collect();
+            let center = label_width + 1 + (x / 2.0) as usize;
+            let start = center.saturating_sub(chars.len() / 2).max(free);
+            if start + chars.len() > labels.len() || start > center {
+                continue;
+            }
+            labels[start..start + chars.len()].copy_from_slice(&chars);
+            free = start + chars.len() + 1;
+        }
+        lines.push(labels.iter().collect::<String>().trim_end().to_string());
+
+        if show_legend {
+            let items: Vec<String> = legend
+                .iter()
+                .map(|(index, name)| format!("{} {name}", paint("⣿", colors[*index])))
+                .collect();
+            lines.push(items.join("  "));
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    /// Render a chart and print it to the standard output.
+    pub fn print(&self, chart: &Chart) -> Result<(), EchartsError> {
+        let text = self.render(chart)?;
+        writeln!(std::io::stdout(), "{text}")?;
+        Ok(())
+    }
+}
+
+enum XScale {
+    Category { count: usize, boundary_gap: bool },
+    Value { min: f64, max: f64, interval: f64 },
+}
+
+impl XScale {
+    /// The dot column of a value on an axis of `width` dots.
+    fn map(&self, value: f64, width: f64) -> f64 {
+        match self {
+            Self::Category {
+                count,
+                boundary_gap: true,
+            } => width * (value + 0.5) / *count as f64,
+            Self::Category { count, .. } if *count > 1 => {
+                (width - 1.0) * value / (*count - 1) as f64
+            }
+            Self::Category { .. } => width / 2.0,
+            Self::Value { min, max, .. } => (width - 1.0) * (value - min) / (max - min),
+        }
+    }
+
+    /// Width of a category in dots.
+    fn band(&self, width: f64) -> f64 {
+        match self {
+            Self::Category { count, .. } => width / *count as f64,
+            Self::Value { .. } => 2.0,
+        }
+    }
+}
+
+/// Braille dots of the plot area, remembering which series drew last into
+/// each character cell to color it.
+struct Canvas {
+    columns: usize,
+    cells: Vec<(u8, Option<usize>)>,
+}
+
+impl Canvas {
+    fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            cells: vec![(0, None); columns * rows],
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.columns * 2
+    }
+
+    fn height(&self) -> usize {
+        self.cells.len() / self.columns * 4
+    }
+
+    fn set(&mut self, x: f64, y: f64, series: usize) {
+        let (x, y) = (x.round(), y.round());
+        if !(0.0..self.width() as f64).contains(&x) || !(0.0..self.height() as f64).contains(&y) {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        let cell = &mut self.cells[y / 4 * self.columns + x / 2];
+        cell.0 |= BRAILLE_DOTS[y % 4][x % 2];
+        cell.1 = Some(series);
+    }
+
+    fn line(&mut self, from: (f64, f64), to: (f64, f64), series: usize) {
+        // Only the part of the line on the canvas is stepped through, a dot at
+        // a time.
+        let Some((from, to)) = self.clip(from, to) else {
+            return;
+        };
+        let steps = (to.0 - from.0)
+            .abs()
+            .max((to.1 - from.1).abs())
+            .ceil()
+            .min((self.width() + self.height()) as f64) as usize;
+        for step in 0..=steps {
+            let t = if steps == 0 {
+                0.0
+            } else {
+                step as f64 / steps as f64
+            };
+            self.set(
+                from.0 + (to.0 - from.0) * t,
+                from.1 + (to.1 - from.1) * t,
+                series,
+            );
+        }
+    }
+
+    /// The part of the line from `from` to `to` that lies on the canvas, if
+    /// any, clipped with the Liang-Barsky algorithm.
+    fn clip(&self, from: (f64, f64), to: (f64, f64)) -> Option<((f64, f64), (f64, f64))> {
+        if ![from.0, from.1, to.0, to.1]
+            .iter()
+            .all(|value| value.is_finite())
+        {
+            return None;
+        }
+        // Dots are rounded, so the canvas spans half a dot past its edges.
+        let (right, bottom) = (self.width() as f64 - 0.5, self.height() as f64 - 0.5);
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let (mut start, mut end) = (0.0_f64, 1.0_f64);
+        for (direction, distance) in [
+            (-dx, from.0 + 0.5),
+            (dx, right - from.0),
+            (-dy, from.1 + 0.5),
+            (dy, bottom - from.1),
+        ] {
+            if direction == 0.0 {
+                if distance < 0.0 {
+                    return None;
+                }
+            } else if direction < 0.0 {
+                start = start.max(distance / direction);
+            } else {
+                end = end.min(distance / direction);
+            }
+        }
+        (start <= end).then_some((
+            (from.0 + dx * start, from.1 + dy * start),
+            (from.0 + dx * end, from.1 + dy * end),
+        ))
+    }
+
+    fn write_row(&self, line: &mut String, row: usize, colors: &[Option<[f64; 3]>]) {
+        for (dots, series) in &self.cells[row * self.columns..(row + 1) * self.columns] {
+            if *dots == 0 {
+                line.push(' ');
+                continue;
+            }
+            let braille = char::from_u32(0x2800 + u32::from(*dots)).unwrap_or(' ');
+            let color = series.and_then(|series| colors[series]);
+            line.push_str(&paint(&braille.to_string(), color));
+        }
+    }
+}
+
+/// Wraps text into the ANSI escape codes of a 24-bit foreground color.
+fn paint(text: &str, color: Option<[f64; 3]>) -> String {
+    match color {
+        Some([r, g, b]) => format!("\x1b[38;2;{r};{g};{b}m{text}\x1b[0m"),
+        None => text.to_string(),
+    }
+}
+
+/// Terminal graphics protocols that display images inline.
+#[cfg(feature = "ssr-raster")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ssr-raster")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerminalImageProtocol {
+    /// The graphics protocol of kitty, also supported by WezTerm and Ghostty.
+    Kitty,
+    /// Sixel graphics, supported by xterm, foot, mlterm and others. Colors are
+    /// reduced to 216 colors.
+    Sixel,
+}
+
+#[cfg(feature = "ssr-raster")]
+impl TerminalImageProtocol {
+    /// The escape sequence that displays the image.
+    pub fn encode(&self, image: &image::RgbaImage) -> String {
+        match self {
+            Self::Kitty => kitty(image),
+            Self::Sixel => sixel(image),
+        }
+    }
+}
+
+#[cfg(feature = "ssr-raster")]
+fn kitty(image: &image::RgbaImage) -> String {
+    // The payload is sent in chunks of at most 4096 bytes.
+    let payload = base64(image.as_raw());
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(4096).collect();
+    let mut output = String::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(index + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if index == 0 {
+            output.push_str(&format!(
+                "\x1b_Ga=T,f=32,s={},v={},m={more};{chunk}\x1b\\",
+                image.width(),
+                image.height()
+            ));
+        } else {
+            output.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    output
+}
+
+#[cfg(feature = "ssr-raster")]
+fn sixel(image: &image::RgbaImage) -> String {
+    // Pixels are reduced to a 6x6x6 color cube, transparent pixels are left
+    // out.
+    let level = |channel: u8| (u32::from(channel) * 5 + 127) / 255;
+    let color = |x: u32, y: u32| {
+        let [r, g, b, a] = image.get_pixel(x, y).0;
+        (a >= 128).then(|| (level(r) * 36 + level(g) * 6 + level(b)) as usize)
+    };
+
+    let (width, height) = image.dimensions();
+    let mut output = format!("\x1bP0;1q\"1;1;{width};{height}");
+    for index in 0..216u32 {
+        let percent = |level: u32| level * 100 / 5;
+        output.push_str(&format!(
+            "#{index};2;{};{};{}",
+            percent(index / 36),
+            percent(index / 6 % 6),
+            percent(index % 6)
+        ));
+    }
+
+    for band in (0..height).step_by(6) {
+        let mut sixels = vec![vec![0u8; width as usize]; 216];
+        let mut used = vec![false; 216];
+        for y in band..(band + 6).min(height) {
+            for x in 0..width {
+                if let Some(index) = color(x, y) {
+                    sixels[index][x as usize] |= 1 << (y - band);
+                    used[index] = true;
+                }
+            }
+        }
+        for (index, row) in sixels.iter().enumerate().filter(|(index, _)| used[*index]) {
+            output.push_str(&format!("#{index}"));
+            let mut x = 0;
+            while x < row.len() {
+                let run = row[x..].iter().take_while(|bits| **bits == row[x]).count();
+                let sixel = char::from(63 + row[x]);
+                if run > 3 {
+                    output.push_str(&format!("!{run}{sixel}"));
+                } else {
+                    output.extend(std::iter::repeat_n(sixel, run));
+                }
+                x += run;
+            }
+            output.push('$');
+        }
+        output.push('-');
+    }
+    output.push_str("\x1b\\");
+    output
+}
+
+#[cfg(feature = "ssr-raster")]
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut output = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let bits = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+        for index in 0..4 {
+            if index <= chunk.len() {
+                output.push(char::from(
+                    ALPHABET[(bits >> (18 - 6 * index) & 0x3f) as usize],
+                ));
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+#[cfg(all(test, feature = "ssr-raster"))]
+mod test {
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+
+    #[test]
+    fn base64_known_vectors() {
+        // The test vectors of RFC 4648, covering both padding cases.
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xfb, 0xff, 0xfe]), "+//+");
+    }
+
+    #[test]
+    fn kitty_sends_small_images_in_one_chunk() {
+        let image = RgbaImage::from_pixel(2, 1, Rgba([255, 0, 0, 255]));
+        assert_eq!(
+            TerminalImageProtocol::Kitty.encode(&image),
+            "\x1b_Ga=T,f=32,s=2,v=1,m=0;/wAA//8AAP8=\x1b\\"
+        );
+    }
+
+    #[test]
+    fn kitty_splits_the_payload_into_chunks() {
+        // 6400 bytes of pixels are 8536 bytes of base64, sent in 3 chunks.
+        let image = RgbaImage::from_pixel(40, 40, Rgba([1, 2, 3, 255]));
+        let output = TerminalImageProtocol::Kitty.encode(&image);
+
+        let commands: Vec<&str> = output
+            .split_terminator("\x1b\\")
+            .map(|command| command.strip_prefix("\x1b_G").unwrap())
+            .collect();
+        assert_eq!(commands.len(), 3);
+        assert!(commands[0].starts_with("a=T,f=32,s=40,v=40,m=1;"));
+        assert!(commands[1].starts_with("m=1;"));
+        assert!(commands[2].starts_with("m=0;"));
+
+        let payloads: Vec<&str> = commands
+            .iter()
+            .map(|command| command.split_once(';').unwrap().1)
+            .collect();
+        assert_eq!(payloads[0].len(), 4096);
+        assert_eq!(payloads[1].len(), 4096);
+        assert_eq!(payloads.concat(), base64(image.as_raw()));
+    }
+
+    #[test]
+    fn sixel_header_and_palette() {
+        let mut image = RgbaImage::new(2, 1);
+        image.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        let output = TerminalImageProtocol::Sixel.encode(&image);
+
+        assert!(output.starts_with("\x1bP0;1q\"1;1;2;1#0;2;0;0;0#1;2;0;0;20"));
+        assert!(output.contains("#180;2;100;0;0"));
+        assert!(output.contains("#215;2;100;100;100"));
+        // Only the red pixel is drawn, the transparent one is left out.
+        assert!(output.ends_with("#215;2;100;100;100#180@?$-\x1b\\"));
+    }
+}
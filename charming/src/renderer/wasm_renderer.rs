@@ -44,6 +44,7 @@ impl WasmRenderer {
             .ok_or(EchartsError::WasmError(format!(
                 "no element with id `{id}` found",
             )))?;
+        Self::register_maps(chart)?;
         let echarts = init(
             &element,
             self.theme.to_str().0,
@@ -51,22 +52,38 @@ impl WasmRenderer {
                 width: self.width,
                 height: self.height,
             })
-            .unwrap(),
+            .map_err(|error| EchartsError::WasmError(error.to_string()))?,
         );
-        Self::update(&echarts, chart);
+        Self::update(&echarts, chart)?;
 
         Ok(echarts)
     }
 
+    /// Registers the [`GeoMap`](crate::component::GeoMap)s of a chart with
+    /// `echarts.registerMap`, so that map and geo components can use them.
+    fn register_maps(chart: &Chart) -> Result<(), EchartsError> {
+        for (name, opt) in chart.geo_maps().filter_map(|m| m.register_args()) {
+            let opt = opt
+                .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+                .map_err(|error| EchartsError::WasmError(error.to_string()))?;
+            register_map(name, opt);
+        }
+        Ok(())
+    }
+
     /// Resizes a chart with options specified in [`ChartResize`]
-    pub fn resize_chart(echarts: &Echarts, chart_size: ChartResize) {
-        echarts
-            .resize(to_value(&chart_size).expect("could not convert resize options to `JsValue`"));
+    pub fn resize_chart(echarts: &Echarts, chart_size: ChartResize) -> Result<(), EchartsError> {
+        let opts =
+            to_value(&chart_size).map_err(|error| EchartsError::WasmError(error.to_string()))?;
+        echarts.resize(opts);
+        Ok(())
     }
 
-    pub fn update(echarts: &Echarts, chart: &Chart) {
-        let js = serde_wasm_bindgen::to_value(&chart).unwrap();
+    pub fn update(echarts: &Echarts, chart: &Chart) -> Result<(), EchartsError> {
+        let js = serde_wasm_bindgen::to_value(&chart)
+            .map_err(|error| EchartsError::WasmError(error.to_string()))?;
         echarts.set_option(js);
+        Ok(())
     }
 }
 
@@ -135,6 +152,9 @@ extern "C" {
     #[wasm_bindgen(js_namespace = echarts, js_name = init)]
     fn init(id: &web_sys::Element, theme: &str, size: JsValue) -> Echarts;
 
+    #[wasm_bindgen(js_namespace = echarts, js_name = registerMap)]
+    fn register_map(name: &str, opt: JsValue);
+
     #[wasm_bindgen(method, js_name = "setOption")]
     fn set_option(this: &Echarts, option: JsValue);
 
@@ -1,11 +1,66 @@
-#![allow(dead_code)]
-
-use crate::element::{
-    ColorBy, CoordinateSystem, Emphasis, Label, LabelLayout, LineStyle, Symbol, Tooltip,
+use crate::{
+    datatype::CompositeValue,
+    element::{
+        Color, ColorBy, CoordinateSystem, Emphasis, Label, LabelLayout, LineStyle, Symbol, Tooltip,
+    },
 };
 use charming_macros::CharmingSetters;
 use serde::{Deserialize, Serialize};
 
+/// Trail animation moving along the lines.
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LinesEffect {
+    show: Option<bool>,
+    /// Time in seconds the effect takes to run along a line.
+    period: Option<f64>,
+    /// Delay in milliseconds before the effect starts.
+    delay: Option<f64>,
+    /// Speed in pixels per second, which overrides `period` so that the
+    /// effect moves equally fast on all lines.
+    constant_speed: Option<f64>,
+    symbol: Option<Symbol>,
+    symbol_size: Option<f64>,
+    color: Option<Color>,
+    /// Length of the trail behind the symbol, from 0 to 1.
+    trail_length: Option<f64>,
+    #[serde(rename = "loop")]
+    loop_: Option<bool>,
+    /// Whether the effect runs back along the line after reaching its end.
+    round_trip: Option<bool>,
+}
+
+/// A line of a [`Lines`] series, from the first to the last of its
+/// coordinates. Lines with more than two coordinates need
+/// [`Lines::polyline`].
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LinesData {
+    name: Option<String>,
+    value: Option<CompositeValue>,
+    /// Coordinates of the start, the waypoints and the end of the line, e.g.
+    /// `[longitude, latitude]` on a geo component.
+    #[charming_set_vec]
+    coords: Vec<CompositeValue>,
+    line_style: Option<LineStyle>,
+    label: Option<Label>,
+    emphasis: Option<Emphasis>,
+}
+
+impl<C: Into<CompositeValue>> From<Vec<C>> for LinesData {
+    fn from(coords: Vec<C>) -> Self {
+        Self::new().coords(coords)
+    }
+}
+
 #[serde_with::apply(
   Option => #[serde(skip_serializing_if = "Option::is_none")],
   Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -33,4 +88,10 @@ pub struct Lines {
     label_layout: Option<LabelLayout>,
     emphasis: Option<Emphasis>,
     tooltip: Option<Tooltip>,
+    effect: Option<LinesEffect>,
+    zlevel: Option<f64>,
+    z: Option<f64>,
+    silent: Option<bool>,
+    #[charming_set_vec]
+    data: Vec<LinesData>,
 }
@@ -1,5 +1,110 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    datatype::{CompositeValue, DataFrame, DataPoint},
+    element::{Blur, Emphasis, ItemStyle, Label, ScaleLimit, Select, Tooltip},
+};
+use charming_macros::CharmingSetters;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone)]
+/// How the values of map series sharing a map are combined into the value
+/// of a region.
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum MapValueCalculation {
+    Sum,
+    Average,
+    Max,
+    Min,
+}
+
+/// Map series, which colors the regions of a map by their values, mostly
+/// together with a [`VisualMap`](crate::component::VisualMap).
+///
+/// The map is one registered with [`Chart::geo_map`](crate::Chart::geo_map),
+/// and the data items are matched to its regions by name.
+///
+/// ```rust
+/// use charming::{Chart, component::VisualMap, df, series::Map};
+///
+/// let chart = Chart::new()
+///     .geo_map(("regions", "<svg>...</svg>"))
+///     .visual_map(VisualMap::new().min(0).max(100).calculable(true))
+///     .series(
+///         Map::new()
+///             .name("Sales")
+///             .map("regions")
+///             .data(df![(80, "North"), (35, "South")]),
+///     );
+/// ```
+#[serde_with::apply(
+  Option => #[serde(skip_serializing_if = "Option::is_none")],
+  Vec => #[serde(default, skip_serializing_if = "Vec::is_empty")]
+)]
+#[derive(Serialize, Deserialize, CharmingSetters, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct Map {}
+pub struct Map {
+    #[serde(rename = "type")]
+    #[charming_type = "map"]
+    type_: String,
+    id: Option<String>,
+    name: Option<String>,
+    /// Name of the registered map.
+    map: Option<String>,
+    /// Whether the map can be zoomed and moved with the mouse.
+    roam: Option<bool>,
+    #[charming_skip_setter]
+    center: Option<(f64, f64)>,
+    aspect_scale: Option<f64>,
+    zoom: Option<f64>,
+    scale_limit: Option<ScaleLimit>,
+    /// Names shown for the regions of the map, by the names in the map.
+    #[charming_skip_setter]
+    name_map: Option<BTreeMap<String, String>>,
+    /// Property of the GeoJSON features used as region names, `name` by
+    /// default.
+    name_property: Option<String>,
+    selected_mode: Option<bool>,
+    label: Option<Label>,
+    item_style: Option<ItemStyle>,
+    emphasis: Option<Emphasis>,
+    select: Option<Select>,
+    blur: Option<Blur>,
+    /// Index of the geo component to draw on, instead of a map of the series
+    /// itself, which lets several series share one map.
+    geo_index: Option<f64>,
+    map_value_calculation: Option<MapValueCalculation>,
+    /// Whether to show a symbol for the series in the legend.
+    show_legend_symbol: Option<bool>,
+    zlevel: Option<f64>,
+    z: Option<f64>,
+    left: Option<CompositeValue>,
+    top: Option<CompositeValue>,
+    right: Option<CompositeValue>,
+    bottom: Option<CompositeValue>,
+    silent: Option<bool>,
+    tooltip: Option<Tooltip>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    data: DataFrame,
+}
+
+impl Map {
+    /// Center of the view, in the coordinates of the map.
+    pub fn center<F: Into<f64>>(mut self, center: (F, F)) -> Self {
+        self.center = Some((center.0.into(), center.1.into()));
+        self
+    }
+
+    pub fn name_map<S: Into<String>, T: Into<String>, I: IntoIterator<Item = (S, T)>>(
+        mut self,
+        name_map: I,
+    ) -> Self {
+        self.name_map = Some(
+            name_map
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        );
+        self
+    }
+}
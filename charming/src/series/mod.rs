@@ -63,6 +63,7 @@ pub enum Series {
     Graph(graph::Graph),
     Heatmap(heatmap::Heatmap),
     Line(line::Line),
+    Lines(lines::Lines),
     Map(map::Map),
     Parallel(parallel::Parallel),
     PictorialBar(pictorial_bar::PictorialBar),
@@ -118,6 +119,7 @@ impl_series_deserialize!(
     Graph => "graph",
     Heatmap => "heatmap",
     Line => "line",
+    Lines => "lines",
     Map => "map",
     Parallel => "parallel",
     PictorialBar => "pictorialBar",
@@ -165,6 +167,7 @@ impl_series!(
     Graph,
     Heatmap,
     Line,
+    Lines,
     Map,
     Parallel,
     PictorialBar,
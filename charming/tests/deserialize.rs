@@ -2,9 +2,12 @@
 mod tests {
     use charming::{
         Chart,
-        component::{Axis, Title},
+        component::{
+            Axis, Brush, BrushMode, BrushSelectType, BrushStyle, BrushThrottleType, BrushType,
+            BrushVisual, Timeline, TimelineControlStyle, Title,
+        },
         element::AxisType,
-        series::Line,
+        series::{Bar, Line, Scatter},
     };
 
     #[test]
@@ -81,6 +84,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_deserialize_chart_with_brush() {
+        let chart = Chart::new()
+            .x_axis(Axis::new().type_(AxisType::Value))
+            .y_axis(Axis::new().type_(AxisType::Value))
+            .brush(
+                Brush::new()
+                    .toolbox(vec![BrushType::Polygon, BrushType::Rect, BrushType::Clear])
+                    .brush_link("all")
+                    .series_index(vec![0, 1])
+                    .x_axis_index(0)
+                    .brush_type(BrushSelectType::Polygon)
+                    .brush_mode(BrushMode::Multiple)
+                    .brush_style(
+                        BrushStyle::new()
+                            .border_width(1)
+                            .color("rgba(120,140,180,0.3)"),
+                    )
+                    .throttle_type(BrushThrottleType::Debounce)
+                    .throttle_delay(300)
+                    .remove_on_click(true)
+                    .in_brush(BrushVisual::new().color(vec!["#c23531"]).symbol_size(12))
+                    .out_of_brush(BrushVisual::new().color_alpha(0.1)),
+            )
+            .series(Scatter::new().data(vec![vec![1, 2], vec![3, 4]]))
+            .series(Scatter::new().data(vec![vec![5, 6]]));
+
+        let chart_str = serde_json::to_string(&chart).expect("Should be able to serialize chart");
+        assert!(chart_str.contains(r#""brushType":"polygon""#));
+        assert!(chart_str.contains(r#""outOfBrush":{"colorAlpha":0.1}"#));
+
+        let chart_deserialized =
+            serde_json::from_str(&chart_str).expect("Should be able to deserialize chart");
+
+        pretty_assertions::assert_eq!(
+            chart,
+            chart_deserialized,
+            "Deserialized chart should be equal to original chart"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_chart_with_timeline() {
+        let chart = Chart::new()
+            .timeline(
+                Timeline::new()
+                    .axis_type(AxisType::Category)
+                    .auto_play(true)
+                    .play_interval(1000)
+                    .control_style(TimelineControlStyle::new().show_prev_btn(false))
+                    .data(vec!["2023", "2024"]),
+            )
+            .x_axis(Axis::new().type_(AxisType::Category).data(vec!["a", "b"]))
+            .y_axis(Axis::new().type_(AxisType::Value))
+            .series(Bar::new())
+            .options(vec![
+                Chart::new()
+                    .title(Title::new().text("2023"))
+                    .series(Bar::new().data(vec![1, 2])),
+                Chart::new()
+                    .title(Title::new().text("2024"))
+                    .series(Bar::new().data(vec![3, 4])),
+            ]);
+
+        let chart_str = serde_json::to_string(&chart).expect("Should be able to serialize chart");
+        assert!(chart_str.contains(r#""options":[{"title":[{"text":"2023"}]"#));
+
+        let chart_deserialized =
+            serde_json::from_str(&chart_str).expect("Should be able to deserialize chart");
+
+        pretty_assertions::assert_eq!(
+            chart,
+            chart_deserialized,
+            "Deserialized chart should be equal to original chart"
+        );
+    }
+
     #[test]
     fn test_deserialize_chart_invalid_axis_category() {
         let incomplete_json =
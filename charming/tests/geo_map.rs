@@ -0,0 +1,81 @@
+#![cfg(feature = "ssr")]
+
+use charming::{
+    Chart, ImageRenderer,
+    component::{Geo, VisualMap, VisualMapChannel},
+    df,
+    series::Map,
+};
+
+const SQUARE_MAP: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+  <!-- a single named region -->
+  <rect name="square" x="10" y="10" width="80" height="80" fill="#123456" />
+</svg>"##;
+
+#[test]
+fn svg_geo_map_renders_through_ssr() {
+    let chart = Chart::new()
+        .geo_map(("square_map", SQUARE_MAP))
+        .geo(Geo::new().map("square_map"));
+
+    let svg = ImageRenderer::new(400, 300).render(&chart).unwrap();
+
+    assert!(svg.contains(r##"fill="#123456""##));
+}
+
+const REGIONS_MAP: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+  <rect name="North" x="0" y="0" width="100" height="100" />
+  <rect name="South" x="100" y="0" width="100" height="100" />
+</svg>"##;
+
+#[test]
+fn map_series_colors_regions_by_value() {
+    let chart = Chart::new()
+        .geo_map(("regions", REGIONS_MAP))
+        .visual_map(
+            VisualMap::new()
+                .show(false)
+                .min(0)
+                .max(100)
+                .in_range(VisualMapChannel::new().color(vec!["#ffffff", "#ff0000"])),
+        )
+        .series(
+            Map::new()
+                .map("regions")
+                .data(df![(100, "North"), (0, "South")]),
+        );
+
+    let svg = ImageRenderer::new(400, 300).render(&chart).unwrap();
+
+    assert!(svg.contains(r#"fill="rgb(255,0,0)""#));
+    assert!(svg.contains(r#"fill="rgb(255,255,255)""#));
+}
+
+const NESTED_MAP: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE svg>
+<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+  <g transform="translate(10, 10)">
+    <path name="North &amp; East" d="M0 0 L80 0 L80 80 Z" />
+  </g>
+</svg>"##;
+
+#[test]
+fn svg_geo_maps_with_groups_and_entities_render_through_ssr() {
+    // The runtime parses SVG maps itself, as there is no DOMParser in V8.
+    let chart = Chart::new()
+        .geo_map(("nested", NESTED_MAP))
+        .visual_map(
+            VisualMap::new()
+                .show(false)
+                .min(0)
+                .max(100)
+                .in_range(VisualMapChannel::new().color(vec!["#ffffff", "#ff0000"])),
+        )
+        .series(Map::new().map("nested").data(df![(100, "North & East")]));
+
+    let svg = ImageRenderer::new(400, 300).render(&chart).unwrap();
+
+    assert!(svg.contains(r#"fill="rgb(255,0,0)""#));
+}
@@ -0,0 +1,120 @@
+use assert_json_diff::assert_json_eq;
+use charming::{
+    Chart,
+    component::{Graphic, GraphicAction, GraphicElement, GraphicShape, GraphicStyle, GraphicType},
+    element::JsFunction,
+};
+use serde_json::json;
+
+#[test]
+fn watermark_and_shapes() {
+    let echarts_json = json!(
+      {
+        "graphic": {
+          "elements": [
+            {
+              "type": "group",
+              "right": 20,
+              "bottom": 20,
+              "z": 100.0,
+              "children": [
+                {
+                  "type": "rect",
+                  "shape": { "width": 120.0, "height": 30.0, "r": [4, 4, 4, 4] },
+                  "style": { "fill": "rgba(0,0,0,0.3)" }
+                },
+                {
+                  "type": "text",
+                  "x": 10.0,
+                  "y": 8.0,
+                  "style": { "text": "ACME Corp.", "fill": "#fff", "font": "bold 14px sans-serif" }
+                }
+              ]
+            },
+            {
+              "type": "bezierCurve",
+              "id": "arrow",
+              "$action": "replace",
+              "shape": { "x1": 0.0, "y1": 0.0, "x2": 100.0, "y2": 100.0, "cpx1": 50.0, "cpy1": 0.0, "cpx2": 50.0, "cpy2": 100.0 },
+              "style": { "stroke": "#c23531", "lineWidth": 2.0, "lineDash": [4.0, 2.0] }
+            },
+            {
+              "type": "polygon",
+              "draggable": true,
+              "shape": { "points": [[0, 0], [10, 0], [5, 10]] }
+            }
+          ]
+        }
+      }
+    );
+
+    let chart = Chart::new().graphic(Graphic::new().elements(vec![
+            GraphicElement::new()
+                .type_(GraphicType::Group)
+                .right(20)
+                .bottom(20)
+                .z(100)
+                .children(vec![
+                    GraphicElement::new()
+                        .type_(GraphicType::Rect)
+                        .shape(GraphicShape::new().width(120).height(30).r(vec![4, 4, 4, 4]))
+                        .style(GraphicStyle::new().fill("rgba(0,0,0,0.3)")),
+                    GraphicElement::new()
+                        .type_(GraphicType::Text)
+                        .x(10)
+                        .y(8)
+                        .style(
+                            GraphicStyle::new()
+                                .text("ACME Corp.")
+                                .fill("#fff")
+                                .font("bold 14px sans-serif"),
+                        ),
+                ]),
+            GraphicElement::new()
+                .type_(GraphicType::BezierCurve)
+                .id("arrow")
+                .action(GraphicAction::Replace)
+                .shape(
+                    GraphicShape::new()
+                        .x1(0)
+                        .y1(0)
+                        .x2(100)
+                        .y2(100)
+                        .cpx1(50)
+                        .cpy1(0)
+                        .cpx2(50)
+                        .cpy2(100),
+                )
+                .style(
+                    GraphicStyle::new()
+                        .stroke("#c23531")
+                        .line_width(2)
+                        .line_dash(vec![4, 2]),
+                ),
+            GraphicElement::new()
+                .type_(GraphicType::Polygon)
+                .draggable(true)
+                .shape(GraphicShape::new().points(vec![vec![0, 0], vec![10, 0], vec![5, 10]])),
+        ]));
+
+    let charming_json =
+        serde_json::from_str::<serde_json::Value>(&chart.to_json().unwrap()).unwrap();
+    assert_json_eq!(echarts_json, charming_json);
+}
+
+#[test]
+fn onclick_is_written_as_function() {
+    let chart = Chart::new().graphic(Graphic::new().elements(vec![
+            GraphicElement::new()
+                .type_(GraphicType::Circle)
+                .shape(GraphicShape::new().cx(50).cy(50).r(20))
+                .onclick(JsFunction::new_with_args("", "alert('clicked');")),
+        ]));
+
+    assert!(
+        chart
+            .to_json()
+            .unwrap()
+            .contains(r#""onclick": function() { alert('clicked'); }"#)
+    );
+}
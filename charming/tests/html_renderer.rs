@@ -0,0 +1,172 @@
+use charming::{
+    Chart, DashboardChart, DashboardLayout, EchartsError, HtmlDashboard, HtmlRenderer,
+    ScriptSource,
+    component::{Axis, Timeline, Title},
+    element::AxisType,
+    series::Line,
+    theme::Theme,
+};
+
+fn line_chart() -> Chart {
+    Chart::new()
+        .x_axis(
+            Axis::new()
+                .type_(AxisType::Category)
+                .data(vec!["Mon", "Tue", "Wed"]),
+        )
+        .y_axis(Axis::new().type_(AxisType::Value))
+        .series(Line::new().data(vec![150, 230, 224]))
+}
+
+#[test]
+fn cdn_script_source() {
+    let html = HtmlRenderer::new("cdn", 800, 600)
+        .render(&line_chart())
+        .unwrap();
+
+    assert!(html.contains(r#"<script src="https://cdn.jsdelivr.net/npm/echarts@5.5.1"#));
+}
+
+#[test]
+fn inline_script_source() {
+    let html = HtmlRenderer::new("inline", 800, 600)
+        .theme(Theme::Westeros)
+        .script_source(ScriptSource::Inline)
+        .render(&line_chart())
+        .unwrap();
+
+    assert!(!html.contains("<script src="));
+    assert!(!html.contains("cdn.jsdelivr.net"));
+    // The bundled echarts library itself is inlined.
+    assert!(html.contains(r#"t.version="5.5.1""#));
+    assert!(html.contains("echarts.registerTheme"));
+    assert!(html.contains("'westeros'"));
+}
+
+#[test]
+fn url_script_source() {
+    let html = HtmlRenderer::new("url", 800, 600)
+        .script_source(ScriptSource::url("static/echarts.min.js"))
+        .render(&line_chart())
+        .unwrap();
+
+    assert!(html.contains(r#"<script src="static/echarts.min.js"></script>"#));
+    assert!(!html.contains("cdn.jsdelivr.net"));
+}
+
+#[test]
+fn closing_script_tags_are_escaped_in_any_case() {
+    let chart = line_chart().title(Title::new().text("</SCRIPT><script>alert(1)</Script>"));
+    let html = HtmlRenderer::new("escape", 800, 600)
+        .render(&chart)
+        .unwrap();
+
+    assert!(html.contains(r#"<\/SCRIPT><script>alert(1)<\/Script>"#));
+    assert!(!html.to_lowercase().contains("</script><script>alert"));
+}
+
+#[test]
+fn geo_maps_are_registered_before_set_option() {
+    let chart = Chart::new().geo_map(("square_map", "<svg></svg>"));
+    let html = HtmlRenderer::new("geo", 800, 600).render(&chart).unwrap();
+
+    let register = html
+        .find(r#"echarts.registerMap("square_map", {"svg":"<svg></svg>"});"#)
+        .unwrap();
+    assert!(register < html.find("chart.setOption").unwrap());
+}
+
+#[test]
+fn geo_maps_of_timeline_frames_are_registered() {
+    let chart = Chart::new()
+        .timeline(Timeline::new().data(vec!["2023"]))
+        .options(vec![Chart::new().geo_map(("square_map", "<svg></svg>"))]);
+    let html = HtmlRenderer::new("geo", 800, 600).render(&chart).unwrap();
+
+    assert!(html.contains(r#"echarts.registerMap("square_map", {"svg":"<svg></svg>"});"#));
+    assert!(html.contains(r#""timeline": {"#));
+    assert!(html.contains(r#""options": ["#));
+}
+
+#[test]
+fn dashboard_loads_echarts_once() {
+    let html = HtmlDashboard::new("dashboard")
+        .layout(DashboardLayout::Grid(2))
+        .chart(DashboardChart::new("cpu", line_chart(), 600, 400).group("metrics"))
+        .chart(DashboardChart::new("memory", line_chart(), 600, 400).group("metrics"))
+        .chart(DashboardChart::new("disk", line_chart(), 300, 200))
+        .render()
+        .unwrap();
+
+    assert_eq!(html.matches("echarts.min.js").count(), 1);
+    assert_eq!(html.matches("echarts.init(").count(), 3);
+    assert!(html.contains(r#"<div id="memory" style="width: 600px; height: 400px"></div>"#));
+    assert!(html.contains("grid-template-columns: repeat(2, max-content)"));
+    assert_eq!(html.matches(r#"echarts.connect("metrics");"#).count(), 1);
+}
+
+#[test]
+fn dashboard_rejects_duplicate_ids() {
+    let result = HtmlDashboard::new("dashboard")
+        .chart(DashboardChart::new("cpu", line_chart(), 600, 400))
+        .chart(DashboardChart::new("cpu", line_chart(), 600, 400))
+        .render();
+
+    assert!(matches!(result, Err(EchartsError::DuplicateIdError(id)) if id == "cpu"));
+}
+
+#[test]
+fn fragment_with_custom_id() {
+    let renderer = HtmlRenderer::new("fragment", 800, 600).responsive(true);
+    let fragment = renderer
+        .render_fragment("sales-chart", &line_chart())
+        .unwrap();
+
+    assert!(!fragment.contains("<html>"));
+    assert!(
+        fragment.contains(
+            r#"<div id="sales-chart" style="width: 100%; aspect-ratio: 800 / 600"></div>"#
+        )
+    );
+    assert!(fragment.contains(r#"document.getElementById("sales-chart")"#));
+    assert!(fragment.contains("chart.resize()"));
+
+    let head = renderer.head().unwrap();
+    assert!(head.contains("echarts.min.js"));
+    assert!(!head.contains("sales-chart"));
+}
+
+#[test]
+fn fragment_ids_must_be_valid() {
+    let renderer = HtmlRenderer::new("fragment", 800, 600);
+    for id in ["", "sales chart"] {
+        assert!(matches!(
+            renderer.render_fragment(id, &line_chart()),
+            Err(EchartsError::InvalidIdError(_))
+        ));
+    }
+
+    let (first, second) = (HtmlRenderer::unique_id(), HtmlRenderer::unique_id());
+    assert_ne!(first, second);
+    assert!(renderer.render_fragment(&first, &line_chart()).is_ok());
+}
+
+#[test]
+fn save_reports_io_errors() {
+    let error = HtmlRenderer::new("io", 800, 600)
+        .save(&line_chart(), "/does/not/exist/chart.html")
+        .unwrap_err();
+
+    assert!(matches!(error, EchartsError::IoError(_)));
+    assert!(std::error::Error::source(&error).is_some());
+}
+
+#[test]
+fn serialization_errors_keep_their_source() {
+    let error: EchartsError = serde_json::from_str::<serde_json::Value>("{")
+        .unwrap_err()
+        .into();
+
+    assert!(matches!(error, EchartsError::SerializationError(_)));
+    assert!(std::error::Error::source(&error).is_some());
+}
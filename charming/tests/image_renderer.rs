@@ -0,0 +1,330 @@
+#![cfg(feature = "ssr")]
+
+use std::time::Duration;
+
+use charming::{
+    Chart, EchartsError, ImageRenderer, RenderOptions,
+    component::Axis,
+    element::{AxisLabel, AxisType, JsFunction},
+    series::Line,
+};
+
+fn line_chart() -> Chart {
+    line_frame(vec![150, 230, 224])
+}
+
+fn line_frame(data: Vec<i32>) -> Chart {
+    Chart::new()
+        .background_color("#ffffff")
+        .x_axis(
+            Axis::new()
+                .type_(AxisType::Category)
+                .data(vec!["Mon", "Tue", "Wed"]),
+        )
+        .y_axis(Axis::new().type_(AxisType::Value))
+        .series(Line::new().data(data))
+}
+
+#[test]
+fn render_with_overrides_renderer_settings() {
+    let mut renderer = ImageRenderer::new(800, 600);
+    let chart = line_chart();
+
+    let thumbnail = renderer
+        .render_with(
+            &chart,
+            &RenderOptions::new()
+                .size(200, 150)
+                .background_color("#ff0000"),
+        )
+        .unwrap();
+    assert!(thumbnail.contains(r#"width="200" height="150""#));
+    assert!(thumbnail.contains(r##"fill="#ff0000""##));
+
+    let full = renderer.render(&chart).unwrap();
+    assert!(full.contains(r#"width="800" height="600""#));
+    assert!(!full.contains(r##"fill="#ff0000""##));
+}
+
+#[test]
+fn invalid_pixel_ratios_are_rejected_before_rendering() {
+    let mut renderer = ImageRenderer::new(400, 300);
+    for pixel_ratio in [f64::NAN, f64::INFINITY, 0.0, -1.0] {
+        let error = renderer
+            .render_with(
+                &line_chart(),
+                &RenderOptions::new().pixel_ratio(pixel_ratio),
+            )
+            .unwrap_err();
+        assert!(matches!(error, EchartsError::InvalidSizeError(_)));
+    }
+
+    let mut renderer = ImageRenderer::new(400, 300).pixel_ratio(f64::NAN);
+    assert!(matches!(
+        renderer.render(&line_chart()),
+        Err(EchartsError::InvalidSizeError(_))
+    ));
+}
+
+#[test]
+fn execution_timeout_terminates_endless_formatters() {
+    let mut renderer = ImageRenderer::new(400, 300).execution_timeout(Duration::from_millis(500));
+    let chart = line_chart().y_axis(Axis::new().type_(AxisType::Value).axis_label(
+        AxisLabel::new().formatter(JsFunction::new_with_args("value", "while (true) {}")),
+    ));
+
+    // The watchdog of the renderer is re-armed for every render.
+    for _ in 0..2 {
+        let error = renderer.render(&chart).unwrap_err();
+        assert!(matches!(
+            error,
+            EchartsError::TimeoutError(timeout) if timeout == Duration::from_millis(500)
+        ));
+
+        // The renderer recovers and keeps rendering.
+        assert!(renderer.render(&line_chart()).unwrap().starts_with("<svg"));
+    }
+}
+
+#[test]
+fn heap_limit_terminates_runaway_allocations() {
+    let mut renderer = ImageRenderer::new(400, 300).heap_limit(128 * 1024 * 1024);
+    let chart = line_chart().y_axis(Axis::new().type_(AxisType::Value).axis_label(
+        AxisLabel::new().formatter(JsFunction::new_with_args(
+            "value",
+            "var chunks = []; while (true) { chunks.push(new Array(1000000).fill(value)); }",
+        )),
+    ));
+
+    let error = renderer.render(&chart).unwrap_err();
+    assert!(matches!(error, EchartsError::HeapLimitError(_)));
+
+    // The renderer recovers and keeps rendering.
+    assert!(renderer.render(&line_chart()).unwrap().starts_with("<svg"));
+}
+
+#[test]
+fn throwing_formatters_are_reported_as_js_exceptions() {
+    let mut renderer = ImageRenderer::new(400, 300);
+    let chart = line_chart().y_axis(Axis::new().type_(AxisType::Value).axis_label(
+        AxisLabel::new().formatter(JsFunction::new_with_args(
+            "value",
+            "throw new Error('formatter failed');",
+        )),
+    ));
+
+    let error = renderer.render(&chart).unwrap_err();
+    let EchartsError::JsException(exception) = error else {
+        panic!("expected a JavaScript exception, got {error:?}");
+    };
+    assert!(exception.message.contains("formatter failed"));
+    assert!(
+        exception
+            .stack
+            .is_some_and(|stack| stack.contains("formatter failed"))
+    );
+}
+
+#[cfg(feature = "ssr-raster")]
+#[test]
+fn pixel_ratio_scales_raster_output() {
+    use charming::ImageFormat;
+
+    let mut renderer = ImageRenderer::new(400, 300).pixel_ratio(2.0);
+    let png = renderer
+        .render_format(ImageFormat::Png, &line_chart())
+        .unwrap();
+    // The size of a PNG image is stored in its IHDR chunk.
+    assert_eq!(png[16..20], 800u32.to_be_bytes());
+    assert_eq!(png[20..24], 600u32.to_be_bytes());
+
+    let png = renderer
+        .render_format_with(
+            ImageFormat::Png,
+            &line_chart(),
+            &RenderOptions::new().pixel_ratio(1.5),
+        )
+        .unwrap();
+    assert_eq!(png[16..20], 600u32.to_be_bytes());
+    assert_eq!(png[20..24], 450u32.to_be_bytes());
+}
+
+#[cfg(feature = "ssr-pdf")]
+#[test]
+fn render_pdf_pages_puts_each_chart_on_its_own_page() {
+    let mut renderer = ImageRenderer::new(400, 300);
+    let pdf = renderer
+        .render_pdf_pages(vec![
+            (line_chart(), RenderOptions::new()),
+            (line_chart(), RenderOptions::new().size(800, 600)),
+        ])
+        .unwrap();
+    let pdf = String::from_utf8_lossy(&pdf);
+
+    assert!(pdf.starts_with("%PDF-"));
+    assert!(pdf.contains("/Count 2"));
+    assert!(pdf.contains("/MediaBox [0 0 400 300]"));
+    assert!(pdf.contains("/MediaBox [0 0 800 600]"));
+}
+
+#[cfg(feature = "ssr-pdf")]
+#[test]
+fn render_pdf_pages_rejects_documents_without_pages() {
+    let mut renderer = ImageRenderer::new(400, 300);
+    assert!(matches!(
+        renderer.render_pdf_pages(Vec::new()),
+        Err(EchartsError::InvalidSizeError(_))
+    ));
+}
+
+#[cfg(feature = "ssr-raster")]
+#[test]
+fn load_font_file_reports_missing_files() {
+    let mut renderer = ImageRenderer::new(400, 300);
+    assert!(renderer.load_font_file("does/not/exist.ttf").is_err());
+}
+
+#[cfg(feature = "ssr-raster")]
+#[test]
+fn render_gif_encodes_a_frame_per_chart() {
+    let frames = vec![
+        line_frame(vec![150, 230, 224]),
+        line_frame(vec![80, 120, 300]),
+        line_frame(vec![200, 100, 50]),
+    ];
+
+    let mut renderer = ImageRenderer::new(200, 150);
+    let gif = renderer
+        .render_gif(&frames, Duration::from_millis(500))
+        .unwrap();
+
+    let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (200, 150));
+    let mut delays = vec![];
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+    }
+    // GIF delays are in hundredths of a second.
+    assert_eq!(delays, vec![50, 50, 50]);
+}
+
+#[cfg(feature = "ssr-raster")]
+#[test]
+fn render_gif_with_overrides_the_size_and_fills_transparency() {
+    // Without a background colour, the chart is transparent around its plot.
+    let chart = Chart::new()
+        .x_axis(
+            Axis::new()
+                .type_(AxisType::Category)
+                .data(vec!["Mon", "Tue", "Wed"]),
+        )
+        .y_axis(Axis::new().type_(AxisType::Value))
+        .series(Line::new().data(vec![150, 230, 224]));
+    let frames = vec![chart.clone(), chart];
+
+    let mut renderer = ImageRenderer::new(200, 150);
+    let gif = renderer
+        .render_gif_with(
+            &frames,
+            Duration::from_millis(100),
+            &RenderOptions::new().size(300, 200),
+        )
+        .unwrap();
+
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(gif.as_slice()).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (300, 200));
+    let frame = decoder.read_next_frame().unwrap().unwrap();
+    assert_eq!(frame.buffer[..4], [255, 255, 255, 255]);
+}
+
+#[cfg(feature = "ssr-raster")]
+#[test]
+fn save_frames_writes_numbered_png_images() {
+    let frames = vec![
+        line_frame(vec![150, 230, 224]),
+        line_frame(vec![80, 120, 300]),
+    ];
+    let dir = std::env::temp_dir().join("charming-save-frames");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut renderer = ImageRenderer::new(200, 150);
+    let paths = renderer.save_frames(&frames, &dir).unwrap();
+    assert_eq!(
+        paths,
+        vec![dir.join("frame-0000.png"), dir.join("frame-0001.png")]
+    );
+    for path in &paths {
+        let png = std::fs::read(path).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+        assert_eq!(png[16..20], 200u32.to_be_bytes());
+        assert_eq!(png[20..24], 150u32.to_be_bytes());
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(all(feature = "ssr-raster", feature = "terminal"))]
+#[test]
+fn render_terminal_encodes_the_raster_image() {
+    use charming::TerminalImageProtocol;
+
+    let mut renderer = ImageRenderer::new(200, 150);
+    let kitty = renderer
+        .render_terminal(TerminalImageProtocol::Kitty, &line_chart())
+        .unwrap();
+    assert!(kitty.starts_with("\x1b_Ga=T,f=32,s=200,v=150,m=1;"));
+    assert!(kitty.ends_with("\x1b\\"));
+
+    let sixel = renderer
+        .render_terminal(TerminalImageProtocol::Sixel, &line_chart())
+        .unwrap();
+    assert!(sixel.starts_with("\x1bP0;1q\"1;1;200;150"));
+    assert!(sixel.ends_with("-\x1b\\"));
+}
+
+#[cfg(feature = "ssr-snapshot")]
+#[test]
+fn built_in_themes_apply_with_the_snapshot() {
+    use charming::theme::Theme;
+
+    // The built-in themes are registered by the snapshot rather than by the
+    // rendered script.
+    let mut renderer = ImageRenderer::new(400, 300).theme(Theme::Westeros);
+    let svg = renderer.render(&line_chart()).unwrap();
+    assert!(svg.contains(r##"stroke="#516b91""##));
+
+    let svg = ImageRenderer::new(400, 300).render(&line_chart()).unwrap();
+    assert!(!svg.contains("#516b91"));
+}
+
+#[test]
+fn render_batch_returns_an_error_per_failing_chart() {
+    let throwing = line_chart().y_axis(Axis::new().type_(AxisType::Value).axis_label(
+        AxisLabel::new().formatter(JsFunction::new_with_args(
+            "value",
+            "throw new Error('formatter failed');",
+        )),
+    ));
+
+    let mut renderer = ImageRenderer::new(400, 300);
+    let results: Vec<_> = renderer
+        .render_batch(vec![
+            (line_chart(), RenderOptions::new()),
+            (throwing, RenderOptions::new()),
+            (line_chart(), RenderOptions::new().size(200, 150)),
+        ])
+        .collect();
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].as_ref().unwrap().starts_with("<svg"));
+    assert!(matches!(results[1], Err(EchartsError::JsException(_))));
+    // The chart that threw was disposed, the next one renders on its own.
+    assert!(
+        results[2]
+            .as_ref()
+            .unwrap()
+            .contains(r#"width="200" height="150""#)
+    );
+}
@@ -0,0 +1,181 @@
+#![cfg(feature = "ssr")]
+
+use std::{
+    future::Future,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::Thread,
+    time::{Duration, Instant},
+};
+
+use charming::{
+    AsyncImageRenderer, Chart, EchartsError, ImageRenderer, ImageRendererPool, RenderOptions,
+    component::Axis,
+    element::{AxisLabel, AxisType, JsFunction},
+    series::Bar,
+};
+
+fn bar_chart() -> Chart {
+    Chart::new()
+        .x_axis(
+            Axis::new()
+                .type_(AxisType::Category)
+                .data(vec!["Mon", "Tue", "Wed"]),
+        )
+        .y_axis(Axis::new().type_(AxisType::Value))
+        .series(Bar::new().data(vec![120, 200, 150]))
+}
+
+fn endless_chart() -> Chart {
+    bar_chart().y_axis(Axis::new().type_(AxisType::Value).axis_label(
+        AxisLabel::new().formatter(JsFunction::new_with_args("value", "while (true) {}")),
+    ))
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[test]
+fn pool_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ImageRendererPool>();
+    assert_send_sync::<AsyncImageRenderer>();
+}
+
+#[test]
+fn pool_renders_from_many_threads() {
+    let pool = Arc::new(ImageRendererPool::new(2, || ImageRenderer::new(400, 300)));
+    assert_eq!(pool.size(), 2);
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let pool = Arc::clone(&pool);
+            std::thread::spawn(move || pool.render(&bar_chart()).unwrap())
+        })
+        .collect();
+
+    for handle in handles {
+        assert!(handle.join().unwrap().starts_with("<svg"));
+    }
+}
+
+#[test]
+fn pool_renders_async() {
+    let pool = ImageRendererPool::new(1, || ImageRenderer::new(400, 300));
+    let svg = block_on(pool.render_async(&bar_chart())).unwrap();
+    assert!(svg.starts_with("<svg"));
+}
+
+#[test]
+fn pool_renders_batch_in_order() {
+    let pool = ImageRendererPool::new(2, || ImageRenderer::new(400, 300));
+    let results = pool.render_batch(
+        (1..=5).map(|width| (bar_chart(), RenderOptions::new().size(width * 100, 200))),
+    );
+
+    assert_eq!(results.len(), 5);
+    for (width, svg) in (1..=5).zip(results) {
+        assert!(svg.unwrap().contains(&format!("width=\"{}\"", width * 100)));
+    }
+}
+
+#[test]
+fn async_renderer_renders_with_options() {
+    let renderer = AsyncImageRenderer::new(400, 300).timeout(Duration::from_secs(30));
+    let svg =
+        block_on(renderer.render_with(&bar_chart(), &RenderOptions::new().size(200, 100))).unwrap();
+    assert!(svg.contains(r#"width="200" height="100""#));
+}
+
+#[test]
+fn pool_timeout_terminates_running_jobs() {
+    let pool =
+        ImageRendererPool::new(1, || ImageRenderer::new(400, 300)).timeout(Duration::from_secs(2));
+    let error = pool.render(&endless_chart()).unwrap_err();
+    assert!(matches!(
+        error,
+        EchartsError::TimeoutError(timeout) if timeout == Duration::from_secs(2)
+    ));
+
+    // The only renderer of the pool is free again, rather than still running
+    // the endless job.
+    assert!(pool.render(&bar_chart()).unwrap().starts_with("<svg"));
+}
+
+#[test]
+fn pool_size_does_not_count_stopped_workers() {
+    let pool = ImageRendererPool::new(1, || panic!("no renderer"));
+    assert!(matches!(
+        pool.render(&bar_chart()),
+        Err(EchartsError::RendererStopped)
+    ));
+
+    let start = Instant::now();
+    while pool.size() > 0 && start.elapsed() < Duration::from_secs(5) {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(pool.size(), 0);
+}
+
+#[test]
+fn polling_a_job_past_its_deadline_times_out() {
+    let pool = ImageRendererPool::new(1, || ImageRenderer::new(400, 300))
+        .timeout(Duration::from_millis(500));
+    let mut job = std::pin::pin!(pool.render_async(&endless_chart()));
+    std::thread::sleep(Duration::from_millis(600));
+
+    let mut context = Context::from_waker(Waker::noop());
+    assert!(matches!(
+        job.as_mut().poll(&mut context),
+        Poll::Ready(Err(EchartsError::TimeoutError(_)))
+    ));
+}
+
+#[test]
+fn dropping_a_running_job_terminates_it() {
+    let pool =
+        ImageRendererPool::new(1, || ImageRenderer::new(400, 300)).timeout(Duration::from_secs(20));
+    let job = pool.render_async(&endless_chart());
+    // Give the only renderer of the pool time to start the endless job.
+    std::thread::sleep(Duration::from_millis(500));
+    drop(job);
+
+    // Without terminating the job, the renderer would only be free again
+    // once the timeout expires.
+    let start = Instant::now();
+    assert!(pool.render(&bar_chart()).unwrap().starts_with("<svg"));
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn awaiting_a_queued_job_times_out_at_its_deadline() {
+    // The only renderer is busy being created for far longer than the
+    // timeout, so the job never leaves the queue before its deadline.
+    let renderer = AsyncImageRenderer::with_renderer(|| {
+        std::thread::sleep(Duration::from_secs(5));
+        ImageRenderer::new(400, 300)
+    })
+    .timeout(Duration::from_millis(500));
+
+    let start = Instant::now();
+    let error = block_on(renderer.render(&bar_chart())).unwrap_err();
+    assert!(matches!(error, EchartsError::TimeoutError(_)));
+    assert!(start.elapsed() < Duration::from_secs(3));
+}
@@ -0,0 +1,77 @@
+use assert_json_diff::assert_json_eq;
+use charming::{
+    Chart,
+    component::Geo,
+    element::{CoordinateSystem, LineStyle, Symbol},
+    series::{Lines, LinesData, LinesEffect, Series},
+};
+use serde_json::json;
+
+#[test]
+fn flight_routes_on_geo() {
+    let echarts_json = json!(
+      {
+        "geo": [{ "map": "world" }],
+        "series": [
+          {
+            "type": "lines",
+            "coordinateSystem": "geo",
+            "polyline": true,
+            "lineStyle": { "width": 1.0, "opacity": 0.4 },
+            "effect": {
+              "show": true,
+              "period": 6.0,
+              "trailLength": 0.7,
+              "symbol": "arrow",
+              "symbolSize": 5.0,
+              "roundTrip": true
+            },
+            "data": [
+              { "coords": [[116.4, 39.9], [121.5, 31.2]] },
+              { "name": "PEK-DXB-LHR", "value": 3, "coords": [[116.4, 39.9], [55.4, 25.3], [-0.5, 51.5]] }
+            ]
+          }
+        ]
+      }
+    );
+
+    let chart = Chart::new().geo(Geo::new().map("world")).series(
+        Lines::new()
+            .coordinate_system(CoordinateSystem::Geo)
+            .polyline(true)
+            .line_style(LineStyle::new().width(1).opacity(0.4))
+            .effect(
+                LinesEffect::new()
+                    .show(true)
+                    .period(6)
+                    .trail_length(0.7)
+                    .symbol(Symbol::Arrow)
+                    .symbol_size(5)
+                    .round_trip(true),
+            )
+            .data(vec![
+                LinesData::from(vec![vec![116.4, 39.9], vec![121.5, 31.2]]),
+                LinesData::new().name("PEK-DXB-LHR").value(3).coords(vec![
+                    vec![116.4, 39.9],
+                    vec![55.4, 25.3],
+                    vec![-0.5, 51.5],
+                ]),
+            ]),
+    );
+
+    let charming_json =
+        serde_json::from_str::<serde_json::Value>(&chart.to_json().unwrap()).unwrap();
+    assert_json_eq!(echarts_json, charming_json);
+
+    let deserialized: Chart = serde_json::from_value(charming_json).unwrap();
+    assert_eq!(chart, deserialized);
+}
+
+#[test]
+fn lines_deserialize_as_lines_series() {
+    let series: Series =
+        serde_json::from_str(r#"{"type": "lines", "data": [{"coords": [[0, 0], [1, 1]]}]}"#)
+            .unwrap();
+
+    assert!(matches!(series, Series::Lines(_)));
+}
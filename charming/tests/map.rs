@@ -0,0 +1,85 @@
+use assert_json_diff::assert_json_eq;
+use charming::{
+    Chart,
+    component::{Geo, VisualMap},
+    df,
+    element::Label,
+    series::{Map, MapValueCalculation, Series},
+};
+use serde_json::json;
+
+#[test]
+fn sales_by_region_choropleth() {
+    let echarts_json = json!(
+      {
+        "visualMap": [{ "min": 0.0, "max": 100.0, "calculable": true }],
+        "series": [
+          {
+            "type": "map",
+            "name": "Sales",
+            "map": "regions",
+            "roam": true,
+            "center": [10.5, 51.0],
+            "zoom": 1.5,
+            "nameMap": { "Bayern": "Bavaria", "Hessen": "Hesse" },
+            "nameProperty": "NAME_1",
+            "mapValueCalculation": "average",
+            "showLegendSymbol": false,
+            "label": { "show": true },
+            "data": [
+              { "value": 80, "name": "Bavaria" },
+              { "value": 35, "name": "Hesse" }
+            ]
+          }
+        ]
+      }
+    );
+
+    let chart = Chart::new()
+        .visual_map(VisualMap::new().min(0).max(100).calculable(true))
+        .series(
+            Map::new()
+                .name("Sales")
+                .map("regions")
+                .roam(true)
+                .center((10.5, 51.0))
+                .zoom(1.5)
+                .name_map([("Bayern", "Bavaria"), ("Hessen", "Hesse")])
+                .name_property("NAME_1")
+                .map_value_calculation(MapValueCalculation::Average)
+                .show_legend_symbol(false)
+                .label(Label::new().show(true))
+                .data(df![(80, "Bavaria"), (35, "Hesse")]),
+        );
+
+    let charming_json =
+        serde_json::from_str::<serde_json::Value>(&chart.to_json().unwrap()).unwrap();
+    assert_json_eq!(echarts_json, charming_json);
+}
+
+#[test]
+fn map_deserializes_as_map_series() {
+    let series: Series = serde_json::from_str(
+        r#"{"type": "map", "map": "regions", "data": [{"value": 1, "name": "North"}]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        series,
+        Series::Map(Map::new().map("regions").data(df![(1, "North")]))
+    );
+}
+
+#[test]
+fn geo_skips_unset_fields() {
+    // echarts does not treat `null` like a missing value, `"show": null`
+    // hides the geo component.
+    let chart = Chart::new().geo(Geo::new().map("regions").roam(true));
+
+    let charming_json =
+        serde_json::from_str::<serde_json::Value>(&chart.to_json().unwrap()).unwrap();
+    assert_json_eq!(
+        json!({ "geo": [{ "map": "regions", "roam": true }] }),
+        charming_json
+    );
+}
@@ -0,0 +1,18 @@
+#![cfg(feature = "evcxr")]
+
+use charming::{Chart, component::Title};
+
+#[test]
+fn notebook_html_loads_echarts_through_require() {
+    let chart = Chart::new().title(Title::new().text("notebook"));
+
+    let first = chart.to_notebook_html().unwrap();
+    let second = chart.to_notebook_html().unwrap();
+
+    let id = |html: &str| html.split('"').nth(1).unwrap().to_string();
+    assert!(id(&first).starts_with("charming-"));
+    assert_ne!(id(&first), id(&second));
+    assert!(first.contains("window.require(['charmingEcharts']"));
+    assert!(first.contains("https://cdn.jsdelivr.net/npm/echarts@5.5.1/dist/echarts.min'"));
+    assert!(first.contains(r#""text": "notebook""#));
+}
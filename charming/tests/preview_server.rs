@@ -0,0 +1,96 @@
+#![cfg(feature = "preview")]
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use charming::{Chart, PreviewServer, component::Title};
+
+fn get(url: &str, path: &str) -> TcpStream {
+    let address = url.trim_start_matches("http://").trim_end_matches('/');
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: {address}\r\n\r\n").unwrap();
+    stream
+}
+
+/// Reads the next server-sent event, skipping the response head.
+fn next_event(reader: &mut impl BufRead) -> String {
+    let mut data = String::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if let Some(line) = line.strip_prefix("data: ") {
+            data.push_str(line);
+        } else if line == "\n" && !data.is_empty() {
+            return data;
+        }
+    }
+}
+
+#[test]
+fn pushes_updates_to_open_pages() {
+    let chart = Chart::new().title(Title::new().text("first"));
+    let preview = PreviewServer::new("preview")
+        .start("127.0.0.1:0", &chart)
+        .unwrap();
+
+    let mut page = String::new();
+    get(&preview.url(), "/").read_to_string(&mut page).unwrap();
+    assert!(page.contains("new EventSource('events')"));
+    assert!(page.contains(r#"<script src="echarts.min.js"></script>"#));
+
+    let mut events = BufReader::new(get(&preview.url(), "/events"));
+    let first = next_event(&mut events);
+    assert!(first.contains("chart.setOption(") && first.contains("\"first\""));
+
+    preview
+        .update(&Chart::new().title(Title::new().text("second")))
+        .unwrap();
+    assert!(next_event(&mut events).contains("\"second\""));
+}
+
+#[test]
+fn serves_the_bundled_echarts_library() {
+    let preview = PreviewServer::new("preview")
+        .start("127.0.0.1:0", &Chart::new())
+        .unwrap();
+
+    let mut response = String::new();
+    get(&preview.url(), "/echarts.min.js")
+        .read_to_string(&mut response)
+        .unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("text/javascript"));
+}
+
+#[test]
+fn updates_do_not_wait_for_pages_that_stop_reading() {
+    let preview = PreviewServer::new("preview")
+        .start("127.0.0.1:0", &Chart::new())
+        .unwrap();
+    let stalled = get(&preview.url(), "/events");
+
+    // Far more than the socket buffers hold, none of which is ever read.
+    let chart = Chart::new().title(Title::new().text("x".repeat(1 << 20)));
+    let (done, finished) = mpsc::channel();
+    let url = preview.url();
+    thread::spawn(move || {
+        for _ in 0..32 {
+            preview.update(&chart).unwrap();
+        }
+        done.send(preview).unwrap();
+    });
+    let preview = finished
+        .recv_timeout(Duration::from_secs(5))
+        .expect("update blocked on a page that stopped reading");
+
+    // New pages still connect while the stalled one is open.
+    let mut events = BufReader::new(get(&url, "/events"));
+    assert!(next_event(&mut events).contains("chart.setOption("));
+    drop(preview);
+    drop(stalled);
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "native-svg")]
+
+use charming::{
+    Chart, EchartsError, SvgRenderer,
+    component::{Axis, Legend, Timeline, Title, VisualMap, VisualMapChannel},
+    df,
+    element::AxisType,
+    series::{Bar, Funnel, Heatmap, Line, Pie, Scatter},
+};
+
+#[test]
+fn bar_chart_uses_echarts_layout() {
+    let chart = Chart::new()
+        .x_axis(
+            Axis::new()
+                .type_(AxisType::Category)
+                .data(vec!["Mon", "Tue", "Wed"]),
+        )
+        .y_axis(Axis::new().type_(AxisType::Value))
+        .series(Bar::new().data(vec![10, 20, 30]));
+
+    let svg = SvgRenderer::new(600, 400).render(&chart).unwrap();
+
+    assert!(svg.starts_with(r#"<svg width="600" height="400""#));
+    assert!(svg.contains(r##"<path d="M84.8 330l110.4 0l0 -90l-110.4 0Z" fill="#5470c6">"##));
+    assert!(svg.contains(r##"transform="translate(52 60)" fill="#6E7079">30</text>"##));
+    assert!(svg.contains(">Wed</text>"));
+}
+
+#[test]
+fn line_and_pie_charts_with_title_and_legend() {
+    let line = Chart::new()
+        .title(Title::new().text("Sales"))
+        .legend(Legend::new())
+        .x_axis(Axis::new().type_(AxisType::Category).data(vec!["a", "b"]))
+        .y_axis(Axis::new().type_(AxisType::Value))
+        .series(Line::new().name("Revenue").data(vec![1, 2]));
+    let svg = SvgRenderer::new(600, 400).render(&line).unwrap();
+    assert!(svg.contains(">Sales</text>"));
+    assert!(svg.contains(">Revenue</text>"));
+    assert!(svg.contains(r##"<path d="M180 195L420 60" fill="none" stroke="#5470c6""##));
+
+    let pie = Chart::new().series(Pie::new().data(df!((1, "x"), (1, "y"))));
+    let svg = SvgRenderer::new(600, 400).render(&pie).unwrap();
+    assert!(svg.contains(r#"<path d="M300 50A150 150 0 0 1 300 350L300 200Z""#));
+    assert!(svg.contains(">y</text>"));
+}
+
+#[test]
+fn timeline_charts_show_the_current_frame() {
+    let chart = Chart::new()
+        .timeline(Timeline::new().current_index(1).data(vec!["2023", "2024"]))
+        .x_axis(Axis::new().type_(AxisType::Category).data(vec!["a", "b"]))
+        .y_axis(Axis::new().type_(AxisType::Value))
+        .series(Line::new().name("Revenue"))
+        .options(vec![
+            Chart::new()
+                .title(Title::new().text("2023"))
+                .series(Line::new().data(vec![2, 1])),
+            Chart::new()
+                .title(Title::new().text("2024"))
+                .series(Line::new().data(vec![1, 2])),
+        ]);
+
+    let svg = SvgRenderer::new(600, 400).render(&chart).unwrap();
+
+    assert!(svg.contains(">2024</text>"));
+    assert!(!svg.contains(">2023</text>"));
+    assert!(svg.contains(r##"<path d="M180 195L420 60" fill="none" stroke="#5470c6""##));
+}
+
+#[test]
+fn unsupported_series_are_rejected() {
+    let chart = Chart::new().series(Funnel::new().data(df!((1, "x"))));
+
+    let error = SvgRenderer::new(600, 400).render(&chart).unwrap_err();
+
+    assert!(matches!(error, EchartsError::ImageRenderingError(_)));
+    assert!(error.to_string().contains("funnel"));
+}
+
+#[test]
+fn huge_and_nan_values_are_skipped() {
+    let chart = Chart::new()
+        .x_axis(Axis::new().type_(AxisType::Value))
+        .y_axis(Axis::new().type_(AxisType::Value))
+        .series(Scatter::new().data(vec![vec![1e20, 5.0], vec![f64::NAN, 1.0], vec![2.0, 3.0]]));
+    let svg = SvgRenderer::new(600, 400).render(&chart).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(!svg.contains("NaN"));
+
+    let chart = Chart::new()
+        .x_axis(Axis::new().type_(AxisType::Category).data(vec!["a", "b"]))
+        .y_axis(Axis::new().type_(AxisType::Value))
+        .series(Scatter::new().data(vec![vec![1e20, 5.0], vec![1.0, 3.0]]))
+        .series(Line::new().data(df![[1e12, 2.0], ["NaN", 4.0]]));
+    let svg = SvgRenderer::new(600, 400).render(&chart).unwrap();
+    assert!(svg.contains(">b</text>"));
+    assert!(!svg.contains("NaN"));
+}
+
+#[test]
+fn unusable_axis_intervals_fall_back_to_nice_ticks() {
+    for interval in [0.0, -10.0, 1e-7] {
+        let chart = Chart::new()
+            .x_axis(Axis::new().type_(AxisType::Category).data(vec!["a", "b"]))
+            .y_axis(Axis::new().type_(AxisType::Value).interval(interval))
+            .series(Bar::new().data(vec![10, 20]));
+
+        let svg = SvgRenderer::new(600, 400).render(&chart).unwrap();
+        assert!(svg.contains(">20</text>"));
+    }
+}
+
+#[test]
+fn non_ascii_colors_are_ignored() {
+    let chart = Chart::new()
+        .x_axis(Axis::new().type_(AxisType::Category).data(vec!["a", "b"]))
+        .y_axis(Axis::new().type_(AxisType::Category).data(vec!["x", "y"]))
+        .visual_map(
+            VisualMap::new()
+                .min(0)
+                .max(10)
+                .in_range(VisualMapChannel::new().color(vec!["#aééb", "#ff0000"])),
+        )
+        .series(Heatmap::new().data(vec![df![0, 0, 5], df![1, 1, 10]]));
+
+    assert!(SvgRenderer::new(600, 400).render(&chart).is_ok());
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "terminal")]
+
+use charming::{
+    Chart, EchartsError, TerminalRenderer,
+    component::{Axis, Title},
+    element::{AxisType, Color},
+    series::{Bar, Line, Pie},
+};
+
+fn chart() -> Chart {
+    Chart::new()
+        .title(Title::new().text("Requests"))
+        .x_axis(
+            Axis::new()
+                .type_(AxisType::Category)
+                .data(vec!["Mon", "Tue", "Wed"]),
+        )
+        .y_axis(Axis::new().type_(AxisType::Value))
+        .series(Line::new().name("api").data(vec![150, 230, 224]))
+        .series(Bar::new().name("web").data(vec![80, 120, 60]))
+}
+
+#[test]
+fn braille_chart_with_axes_and_legend() {
+    let text = TerminalRenderer::new(40, 12)
+        .colors(false)
+        .render(&chart())
+        .unwrap();
+
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 12);
+    assert_eq!(lines[0], "Requests");
+    assert!(lines[1].starts_with("250┤"));
+    assert!(lines.iter().any(|line| line.starts_with("  0┤")));
+    assert!(lines[10].contains("Mon") && lines[10].contains("Wed"));
+    assert_eq!(lines[11], "⣿ api  ⣿ web");
+    assert!(lines.iter().all(|line| line.chars().count() <= 40));
+    assert!(text.chars().any(|c| ('\u{2801}'..='\u{28ff}').contains(&c)));
+    assert!(!text.contains('\x1b'));
+}
+
+#[test]
+fn colors_use_ansi_escape_codes() {
+    let text = TerminalRenderer::new(40, 12).render(&chart()).unwrap();
+
+    assert!(text.contains("\x1b[38;2;84;112;198m"));
+}
+
+#[test]
+fn unsupported_series_are_rejected() {
+    let chart = Chart::new().series(Pie::new());
+
+    let error = TerminalRenderer::new(40, 12).render(&chart).unwrap_err();
+
+    assert!(matches!(error, EchartsError::ImageRenderingError(_)));
+}
+
+#[test]
+fn far_away_points_are_clipped() {
+    let chart = Chart::new()
+        .x_axis(
+            Axis::new()
+                .type_(AxisType::Category)
+                .data(vec!["Mon", "Tue", "Wed"]),
+        )
+        .y_axis(Axis::new().type_(AxisType::Value).max(100))
+        .series(Line::new().data(vec![50.0, 1e12, f64::NAN, 75.0]))
+        .series(Line::new().data(vec![vec![1e20, 5.0], vec![1.0, 80.0]]));
+
+    let text = TerminalRenderer::new(40, 12)
+        .colors(false)
+        .render(&chart)
+        .unwrap();
+    assert!(text.lines().any(|line| line.starts_with("100┤")));
+}
+
+#[test]
+fn unusable_axis_intervals_fall_back_to_nice_ticks() {
+    for interval in [0.0, -10.0, 1e-7] {
+        let chart = Chart::new()
+            .x_axis(Axis::new().type_(AxisType::Value).interval(interval))
+            .y_axis(Axis::new().type_(AxisType::Value).interval(interval))
+            .series(Line::new().data(vec![vec![0, 10], vec![10, 20]]));
+
+        let text = TerminalRenderer::new(40, 12)
+            .colors(false)
+            .render(&chart)
+            .unwrap();
+        assert!(text.lines().any(|line| line.starts_with("20┤")));
+    }
+}
+
+#[test]
+fn non_ascii_colors_are_ignored() {
+    let chart = chart().color(vec![Color::from("#aééb"), Color::from("#ff0000")]);
+    assert!(TerminalRenderer::new(40, 12).render(&chart).is_ok());
+}
@@ -0,0 +1,33 @@
+/*!
+Creates the V8 startup snapshot embedded into charming's `ImageRenderer` when
+the `ssr-snapshot` feature is enabled. It is used from charming's build script,
+and lives in its own crate so that `deno_core` is only built for the host when
+the snapshot is actually requested.
+*/
+use deno_core::snapshot::{CreateSnapshotOptions, create_snapshot};
+
+/// Evaluate the given `(name, source)` scripts in a fresh runtime and return
+/// the snapshot of the resulting heap.
+pub fn create(scripts: Vec<(String, String)>) -> Result<Box<[u8]>, String> {
+    let output = create_snapshot(
+        CreateSnapshotOptions {
+            cargo_manifest_dir: env!("CARGO_MANIFEST_DIR"),
+            startup_snapshot: None,
+            skip_op_registration: false,
+            extensions: vec![],
+            extension_transpiler: None,
+            with_runtime_cb: Some(Box::new(move |runtime| {
+                for (name, source) in &scripts {
+                    if let Err(error) = runtime.execute_script(format!("[{name}]"), source.clone())
+                    {
+                        panic!("failed to evaluate {name}: {error}");
+                    }
+                }
+            })),
+        },
+        None,
+    )
+    .map_err(|error| error.to_string())?;
+
+    Ok(output.output)
+}
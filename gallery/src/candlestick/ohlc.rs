@@ -1,7 +1,7 @@
 use charming::{
     Chart,
     component::{
-        Axis, Brush, BrushType, DataZoom, DataZoomType, Feature, Grid, Legend, Toolbox,
+        Axis, BrushType, DataZoom, DataZoomType, Feature, Grid, Legend, Toolbox, ToolboxBrush,
         ToolboxDataZoom,
     },
     element::{
@@ -41,7 +41,7 @@ pub fn chart() -> Chart {
             Toolbox::new().feature(
                 Feature::new()
                     .data_zoom(ToolboxDataZoom::new().y_axis_index("none"))
-                    .brush(Brush::new().type_(vec![BrushType::LineX, BrushType::Clear])),
+                    .brush(ToolboxBrush::new().type_(vec![BrushType::LineX, BrushType::Clear])),
             ),
         )
         .grid(Grid::new().left("10%").right("8%").bottom(150))